@@ -0,0 +1,111 @@
+use crate::window::WindowContents;
+use crate::{Error, Settings, Window};
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// Identifies which window an [`Event`] happened in, see [`Window::id`]
+///
+/// Events that don't belong to any window, like gamepad input, are tagged with the main window.
+///
+/// [`Event`]: crate::Event
+/// [`Window::id`]: crate::Window::id
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowId(pub(crate) u32);
+
+impl WindowId {
+    /// The window passed to the app by [`run`](crate::run())
+    pub(crate) const MAIN: WindowId = WindowId(0);
+}
+
+/// The child windows of an app, shared by all of its windows
+///
+/// Child windows can only be created inside the event loop, so [`Window::open_child`] queues a
+/// request that the loop fulfills at the end of the frame.
+///
+/// [`Window::open_child`]: crate::Window::open_child
+pub(crate) struct WindowRegistry {
+    children: RefCell<Vec<Weak<WindowContents>>>,
+    requests: RefCell<Vec<Rc<RefCell<ChildRequest>>>>,
+    next_id: Cell<u32>,
+}
+
+pub(crate) struct ChildRequest {
+    settings: Option<Settings>,
+    result: Option<Result<Window, Error>>,
+    waker: Option<Waker>,
+}
+
+impl WindowRegistry {
+    pub fn new() -> WindowRegistry {
+        WindowRegistry {
+            children: RefCell::new(Vec::new()),
+            requests: RefCell::new(Vec::new()),
+            next_id: Cell::new(WindowId::MAIN.0 + 1),
+        }
+    }
+
+    pub fn next_id(&self) -> WindowId {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        WindowId(id)
+    }
+
+    pub fn add(&self, child: &Rc<WindowContents>) {
+        let mut children = self.children.borrow_mut();
+        // Windows whose handles were all dropped are gone for good
+        children.retain(|child| child.strong_count() > 0);
+        children.push(Rc::downgrade(child));
+    }
+
+    /// The open child windows, in the order they were created
+    pub fn children(&self) -> Vec<Rc<WindowContents>> {
+        self.children
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    pub fn request(&self, settings: Settings) -> Rc<RefCell<ChildRequest>> {
+        let request = Rc::new(RefCell::new(ChildRequest {
+            settings: Some(settings),
+            result: None,
+            waker: None,
+        }));
+        self.requests.borrow_mut().push(request.clone());
+
+        request
+    }
+
+    /// Take the requests that haven't been fulfilled yet
+    pub fn take_requests(&self) -> Vec<Rc<RefCell<ChildRequest>>> {
+        std::mem::take(&mut *self.requests.borrow_mut())
+    }
+}
+
+impl ChildRequest {
+    pub fn take_settings(&mut self) -> Settings {
+        self.settings
+            .take()
+            .expect("Internal error: child window requested twice")
+    }
+
+    pub fn fulfill(&mut self, result: Result<Window, Error>) {
+        self.result = Some(result);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    pub fn poll(&mut self, cx: &mut Context) -> Poll<Result<Window, Error>> {
+        match self.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
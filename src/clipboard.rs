@@ -0,0 +1,111 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use self::desktop::Clipboard;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use self::web::{read, write, PasteListener};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod desktop {
+    use std::cell::RefCell;
+    use winit::window::Window as WinitWindow;
+
+    /// The system clipboard, connected the first time it's used
+    pub(crate) struct Clipboard(RefCell<Option<window_clipboard::Clipboard>>);
+
+    impl Clipboard {
+        pub fn new() -> Clipboard {
+            Clipboard(RefCell::new(None))
+        }
+
+        pub fn read(&self, window: &WinitWindow) -> Option<String> {
+            self.with(window, |clipboard| clipboard.read().ok())
+        }
+
+        pub fn write(&self, window: &WinitWindow, text: &str) -> bool {
+            self.with(window, |clipboard| clipboard.write(text.to_owned()).ok())
+                .is_some()
+        }
+
+        fn with<T>(
+            &self,
+            window: &WinitWindow,
+            f: impl FnOnce(&mut window_clipboard::Clipboard) -> Option<T>,
+        ) -> Option<T> {
+            let mut clipboard = self.0.borrow_mut();
+            if clipboard.is_none() {
+                *clipboard = window_clipboard::Clipboard::connect(window).ok();
+            }
+
+            f(clipboard.as_mut()?)
+        }
+    }
+}
+
+/// The browser's async Clipboard API, looked up dynamically because `web-sys` only exposes it
+/// behind `--cfg=web_sys_unstable_apis`
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// Call a method of `navigator.clipboard`, and wait for the promise it returns
+    async fn call(method: &str, arg: Option<&str>) -> Option<JsValue> {
+        let navigator = web_sys::window()?.navigator();
+        let clipboard = js_sys::Reflect::get(&navigator, &"clipboard".into()).ok()?;
+        let function: js_sys::Function = js_sys::Reflect::get(&clipboard, &method.into())
+            .ok()?
+            .dyn_into()
+            .ok()?;
+        let promise = match arg {
+            Some(arg) => function.call1(&clipboard, &arg.into()),
+            None => function.call0(&clipboard),
+        };
+        let promise: js_sys::Promise = promise.ok()?.dyn_into().ok()?;
+
+        JsFuture::from(promise).await.ok()
+    }
+
+    pub(crate) async fn read() -> Option<String> {
+        call("readText", None).await?.as_string()
+    }
+
+    pub(crate) async fn write(text: &str) -> bool {
+        call("writeText", Some(text)).await.is_some()
+    }
+
+    /// Collects the text of the paste events sent to a canvas
+    pub(crate) struct PasteListener {
+        pasted: Rc<RefCell<Vec<String>>>,
+        _listener: Closure<dyn FnMut(web_sys::Event)>,
+    }
+
+    impl PasteListener {
+        pub fn listen(canvas: &web_sys::HtmlCanvasElement) -> Result<PasteListener, JsValue> {
+            let pasted = Rc::new(RefCell::new(Vec::new()));
+            let queue = pasted.clone();
+            let listener = Closure::wrap(Box::new(move |event: web_sys::Event| {
+                let text = js_sys::Reflect::get(&event, &"clipboardData".into())
+                    .ok()
+                    .and_then(|data| data.dyn_into::<web_sys::DataTransfer>().ok())
+                    .and_then(|data| data.get_data("text").ok());
+                if let Some(text) = text {
+                    event.prevent_default();
+                    queue.borrow_mut().push(text);
+                }
+            }) as Box<dyn FnMut(web_sys::Event)>);
+            canvas.add_event_listener_with_callback("paste", listener.as_ref().unchecked_ref())?;
+
+            Ok(PasteListener {
+                pasted,
+                _listener: listener,
+            })
+        }
+
+        /// The text pasted since the last call
+        pub fn take(&self) -> Vec<String> {
+            std::mem::take(&mut *self.pasted.borrow_mut())
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Error;
+use mint::Vector2;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// A cursor drawn from an image, set with [`CursorIcon::Custom`]
+///
+/// On web the image becomes a CSS `cursor: url()`. Desktop platforms can't show custom cursors
+/// yet, so there the system cursor is hidden and the app should draw [`Window::software_cursor`]
+/// itself, at the pointer location minus the [`hotspot`].
+///
+/// Cloning a `CustomCursor` is cheap, and clones compare equal to each other.
+///
+/// [`CursorIcon::Custom`]: crate::CursorIcon::Custom
+/// [`Window::software_cursor`]: crate::Window::software_cursor
+/// [`hotspot`]: CustomCursor::hotspot
+#[derive(Clone)]
+pub struct CustomCursor(Arc<CursorImage>);
+
+struct CursorImage {
+    rgba: Vec<u8>,
+    size: Vector2<u32>,
+    hotspot: Vector2<u32>,
+}
+
+impl CustomCursor {
+    /// Create a cursor from 8-bit RGBA pixels, in rows from the top left
+    ///
+    /// The hotspot is the pixel that points at things, like the tip of an arrow. It fails if
+    /// `rgba` isn't `width * height * 4` bytes, or the hotspot is outside of the image.
+    pub fn from_rgba(
+        rgba: Vec<u8>,
+        size: Vector2<u32>,
+        hotspot: Vector2<u32>,
+    ) -> Result<CustomCursor, Error> {
+        if rgba.len() != size.x as usize * size.y as usize * 4 {
+            return Err(Error::CursorLoad(
+                format!(
+                    "expected {} bytes of RGBA data for a {}x{} image, got {}",
+                    size.x as usize * size.y as usize * 4,
+                    size.x,
+                    size.y,
+                    rgba.len()
+                )
+                .into(),
+            ));
+        }
+        if hotspot.x >= size.x || hotspot.y >= size.y {
+            return Err(Error::CursorLoad(
+                format!(
+                    "the hotspot ({}, {}) is outside of the {}x{} image",
+                    hotspot.x, hotspot.y, size.x, size.y
+                )
+                .into(),
+            ));
+        }
+
+        Ok(CustomCursor(Arc::new(CursorImage {
+            rgba,
+            size,
+            hotspot,
+        })))
+    }
+
+    /// Load a cursor from an image file
+    ///
+    /// Browsers can't read files, so on web use [`from_memory`] with the fetched bytes instead.
+    ///
+    /// [`from_memory`]: CustomCursor::from_memory
+    #[cfg(all(feature = "image", not(target_arch = "wasm32")))]
+    pub fn from_path(path: &str, hotspot: Vector2<u32>) -> Result<CustomCursor, Error> {
+        let (rgba, size) =
+            crate::icon::load_path(path).map_err(|err| Error::CursorLoad(Box::new(err)))?;
+        CustomCursor::from_rgba(rgba, size, hotspot)
+    }
+
+    /// Load a cursor from the contents of an image file, in any format `image` was built with
+    #[cfg(feature = "image")]
+    pub fn from_memory(bytes: &[u8], hotspot: Vector2<u32>) -> Result<CustomCursor, Error> {
+        let (rgba, size) =
+            crate::icon::load_memory(bytes).map_err(|err| Error::CursorLoad(Box::new(err)))?;
+        CustomCursor::from_rgba(rgba, size, hotspot)
+    }
+
+    /// The 8-bit RGBA pixels of the image, in rows from the top left
+    pub fn rgba(&self) -> &[u8] {
+        &self.0.rgba
+    }
+
+    /// The size of the image in pixels
+    pub fn size(&self) -> Vector2<u32> {
+        self.0.size
+    }
+
+    /// The pixel of the image that points at things
+    pub fn hotspot(&self) -> Vector2<u32> {
+        self.0.hotspot
+    }
+
+    /// The value for a CSS `cursor` property that shows this image
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn css(&self) -> Option<String> {
+        let url = crate::icon::data_url(&self.0.rgba, self.0.size)?;
+
+        Some(format!(
+            "url({}) {} {}, auto",
+            url, self.0.hotspot.x, self.0.hotspot.y
+        ))
+    }
+}
+
+impl PartialEq for CustomCursor {
+    fn eq(&self, other: &CustomCursor) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CustomCursor {}
+
+impl Hash for CustomCursor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as usize).hash(state);
+    }
+}
@@ -0,0 +1,60 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+type Source = Box<dyn StdError + Send + Sync>;
+
+/// The ways starting a blinds app can fail, returned by [`try_run`]
+///
+/// [`try_run`]: crate::try_run()
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The operating system or browser couldn't create the window
+    WindowCreation(Source),
+    /// An OpenGL context matching the [`Settings`] couldn't be created or made current
+    ///
+    /// [`Settings`]: crate::Settings
+    ContextCreation(Source),
+    /// The window icon couldn't be loaded or wasn't valid image data
+    IconLoad(Source),
+    /// A [`CustomCursor`] couldn't be loaded or wasn't valid image data
+    ///
+    /// [`CustomCursor`]: crate::CustomCursor
+    CursorLoad(Source),
+    /// A browser DOM operation failed, like inserting the canvas into the page
+    Dom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::WindowCreation(err) => write!(f, "failed to create the window: {}", err),
+            Error::ContextCreation(err) => write!(f, "failed to create a GL context: {}", err),
+            Error::IconLoad(err) => write!(f, "failed to load the window icon: {}", err),
+            Error::CursorLoad(err) => write!(f, "failed to load the cursor: {}", err),
+            Error::Dom(msg) => write!(f, "failed to set up the page: {}", msg),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::WindowCreation(err)
+            | Error::ContextCreation(err)
+            | Error::IconLoad(err)
+            | Error::CursorLoad(err) => Some(err.as_ref()),
+            Error::Dom(_) => None,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<glutin::CreationError> for Error {
+    fn from(err: glutin::CreationError) -> Error {
+        match err {
+            glutin::CreationError::Window(err) => Error::WindowCreation(Box::new(err)),
+            err => Error::ContextCreation(Box::new(err)),
+        }
+    }
+}
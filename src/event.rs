@@ -1,10 +1,15 @@
+use crate::FullscreenMode;
 use mint::Vector2;
 mod convert;
+mod file;
 mod gamepad;
 mod keyboard;
 mod pointer;
 
 pub(crate) use self::convert::*;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use self::file::DropListener;
+pub use self::file::*;
 pub use self::gamepad::*;
 pub use self::keyboard::*;
 pub use self::pointer::*;
@@ -17,6 +22,10 @@ pub enum Event {
     ///
     /// [`Window::size`]: crate::Window::size
     Resized(ResizedEvent),
+    /// The window has moved on the desktop, see [`Window::position`]
+    ///
+    /// [`Window::position`]: crate::Window::position
+    Moved(MovedEvent),
     /// The scale factor of the window has changed, see [`Window::scale_factor`]
     ///
     /// [`Window::scale_factor`]: crate::Window::scale_factor
@@ -39,12 +48,27 @@ pub enum Event {
     PointerLeft(PointerLeftEvent),
     /// A pointer has a new position, relative to the window's top-left
     PointerMoved(PointerMovedEvent),
+    /// The mouse moved by some amount, without being limited by the edges of the window
+    ///
+    /// This is the raw motion of the device, so it's suited for controls like mouselook,
+    /// especially along with [`CursorGrab::Locked`]. It is only delivered to the focused window.
+    ///
+    /// [`CursorGrab::Locked`]: crate::CursorGrab::Locked
+    PointerDelta(PointerDeltaEvent),
     /// A button on a pointer, likely a mouse, has produced an input
     PointerInput(PointerInputEvent),
     /// The mousewheel has scrolled, either in lines or pixels (depending on the input method)
     ScrollInput(ScrollDelta),
     /// The keyboard modifiers (e.g. shift, alt, ctrl) have changed
     ModifiersChanged(ModifiersChangedEvent),
+    /// The user pasted some text into the page
+    ///
+    /// Only browsers deliver this, when the canvas is focused and the `clipboard` feature is
+    /// enabled. On desktop, watch for [`Key::Paste`] or the usual shortcut and read
+    /// [`Window::clipboard_text`] instead.
+    ///
+    /// [`Window::clipboard_text`]: crate::Window::clipboard_text
+    Paste(String),
     /// A gamepad has been connected
     GamepadConnected(GamepadConnectedEvent),
     /// A gamepad has been disconnected
@@ -53,6 +77,29 @@ pub enum Event {
     GamepadButton(GamepadButtonEvent),
     /// A gamepad axis has changed its value
     GamepadAxis(GamepadAxisEvent),
+    /// The user asked to close the window, for example with its close button
+    ///
+    /// If [`Settings::exit_on_close`] is true, the app exits after this frame. Otherwise, it keeps
+    /// running until [`Window::close`] is called, so it can ask to save changes first.
+    ///
+    /// [`Settings::exit_on_close`]: crate::Settings::exit_on_close
+    /// [`Window::close`]: crate::Window::close
+    CloseRequested,
+    /// The window entered or left fullscreen, or moved to another monitor or video mode
+    ///
+    /// This is also delivered when the platform changes the mode, like a browser leaving
+    /// fullscreen when the user presses escape.
+    FullscreenChanged(FullscreenChangedEvent),
+    /// The user is dragging a file over the window
+    ///
+    /// Dragging several files delivers one event for each on desktop, but only one on web.
+    FileHovered(FileHoveredEvent),
+    /// The user dropped a file onto the window
+    ///
+    /// Dropping several files delivers one event for each.
+    FileDropped(FileDroppedEvent),
+    /// The user dragged the files away from the window without dropping them
+    FileHoverCancelled,
 }
 
 #[derive(Clone, Debug)]
@@ -68,6 +115,19 @@ impl ResizedEvent {
     }
 }
 
+#[derive(Clone, Debug)]
+/// See [`Event::Moved`]
+pub struct MovedEvent {
+    pub(crate) position: Vector2<i32>,
+}
+
+impl MovedEvent {
+    /// The new position of the window's top-left corner, in physical pixels
+    pub fn position(&self) -> Vector2<i32> {
+        self.position
+    }
+}
+
 #[derive(Clone, Debug)]
 /// See [`Event::ScaleFactorChanged`]
 pub struct ScaleFactorChangedEvent {
@@ -105,15 +165,31 @@ impl ReceivedCharacterEvent {
     }
 }
 
+#[derive(Clone, Debug)]
+/// See [`Event::FullscreenChanged`]
+pub struct FullscreenChangedEvent {
+    pub(crate) mode: FullscreenMode,
+}
+
+impl FullscreenChangedEvent {
+    /// The mode the window is now in
+    ///
+    /// Monitors are given by index, even if the mode was requested with None, except when running
+    /// headless.
+    pub fn mode(&self) -> FullscreenMode {
+        self.mode
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 /// A change in the event modifiers like shift, control, alt, or 'logo'
 ///
 /// See [`Event::ModifiersChanged`]
 pub struct ModifiersChangedEvent {
-    shift: bool,
-    ctrl: bool,
-    alt: bool,
-    logo: bool,
+    pub(crate) shift: bool,
+    pub(crate) ctrl: bool,
+    pub(crate) alt: bool,
+    pub(crate) logo: bool,
 }
 
 impl ModifiersChangedEvent {
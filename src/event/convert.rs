@@ -1,6 +1,6 @@
 use super::*;
 use crate::window::WindowContents;
-use winit::event::{ElementState, WindowEvent};
+use winit::event::{DeviceEvent, ElementState, WindowEvent};
 
 pub(crate) fn window_event(event: WindowEvent, window: &WindowContents) -> Option<Event> {
     use WindowEvent::*;
@@ -8,6 +8,12 @@ pub(crate) fn window_event(event: WindowEvent, window: &WindowContents) -> Optio
         Resized(ls) => Event::Resized(ResizedEvent {
             size: ps_to_logical_vec(ls, window.scale()),
         }),
+        Moved(position) => Event::Moved(MovedEvent {
+            position: Vector2 {
+                x: position.x,
+                y: position.y,
+            },
+        }),
         ScaleFactorChanged { scale_factor, .. } => {
             Event::ScaleFactorChanged(ScaleFactorChangedEvent {
                 scale: scale_factor as f32,
@@ -32,13 +38,15 @@ pub(crate) fn window_event(event: WindowEvent, window: &WindowContents) -> Optio
             position,
             ..
         } => Event::PointerMoved(PointerMovedEvent {
-            id: PointerId(device_id),
+            id: PointerId(PointerSource::Device(device_id)),
             location: pp_to_logical_vec(position, window.scale()),
         }),
-        CursorEntered { device_id, .. } => {
-            Event::PointerEntered(PointerEnteredEvent(PointerId(device_id)))
-        }
-        CursorLeft { device_id, .. } => Event::PointerLeft(PointerLeftEvent(PointerId(device_id))),
+        CursorEntered { device_id, .. } => Event::PointerEntered(PointerEnteredEvent(PointerId(
+            PointerSource::Device(device_id),
+        ))),
+        CursorLeft { device_id, .. } => Event::PointerLeft(PointerLeftEvent(PointerId(
+            PointerSource::Device(device_id),
+        ))),
         MouseWheel { delta, .. } => Event::ScrollInput(delta.into()),
         MouseInput {
             device_id,
@@ -46,20 +54,40 @@ pub(crate) fn window_event(event: WindowEvent, window: &WindowContents) -> Optio
             state,
             ..
         } => Event::PointerInput(PointerInputEvent {
-            id: PointerId(device_id),
+            id: PointerId(PointerSource::Device(device_id)),
             button: button.into(),
             is_down: state == ElementState::Pressed,
         }),
         ModifiersChanged(state) => Event::ModifiersChanged(convert_modifiers(state)),
+        CloseRequested => Event::CloseRequested,
+        HoveredFile(path) => Event::FileHovered(FileHoveredEvent { path: Some(path) }),
+        DroppedFile(path) => Event::FileDropped(FileDroppedEvent {
+            file: self::DroppedFile::Path(path),
+        }),
+        HoveredFileCancelled => Event::FileHoverCancelled,
         _ => return None,
     })
 }
 
+pub(crate) fn device_event(event: DeviceEvent) -> Option<Event> {
+    match event {
+        DeviceEvent::MouseMotion { delta: (x, y) } => {
+            Some(Event::PointerDelta(PointerDeltaEvent {
+                delta: Vector2 {
+                    x: x as f32,
+                    y: y as f32,
+                },
+            }))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(feature = "gilrs")]
 pub(crate) fn gamepad_event(event: gilrs::Event) -> Option<Event> {
     use gilrs::ev::EventType::*;
     let gilrs::Event { id, event, .. } = event;
-    let id = GamepadId(id);
+    let id = GamepadId(id.into());
     Some(match event {
         ButtonPressed(btn, _) => Event::GamepadButton(GamepadButtonEvent {
             id,
@@ -0,0 +1,161 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug)]
+/// See [`Event::FileHovered`]
+///
+/// [`Event::FileHovered`]: crate::event::Event::FileHovered
+pub struct FileHoveredEvent {
+    pub(crate) path: Option<PathBuf>,
+}
+
+impl FileHoveredEvent {
+    /// The path of the file being dragged
+    ///
+    /// Browsers keep the files secret until they're dropped, so this is None on web.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+#[derive(Clone, Debug)]
+/// See [`Event::FileDropped`]
+///
+/// [`Event::FileDropped`]: crate::event::Event::FileDropped
+pub struct FileDroppedEvent {
+    pub(crate) file: DroppedFile,
+}
+
+/// Where the dropped file can be read from
+#[derive(Clone, Debug)]
+pub(crate) enum DroppedFile {
+    Path(PathBuf),
+    #[cfg(target_arch = "wasm32")]
+    Web(web_sys::File),
+    /// A file that was dropped into a browser, replayed from a recording that only kept its name
+    Name(String),
+}
+
+impl FileDroppedEvent {
+    /// The name of the file, without the directories that lead to it
+    pub fn name(&self) -> String {
+        match &self.file {
+            DroppedFile::Path(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            #[cfg(target_arch = "wasm32")]
+            DroppedFile::Web(file) => file.name(),
+            DroppedFile::Name(name) => name.clone(),
+        }
+    }
+
+    /// The path of the file, which is None for files dropped into a browser
+    pub fn path(&self) -> Option<&Path> {
+        match &self.file {
+            DroppedFile::Path(path) => Some(path),
+            #[cfg(target_arch = "wasm32")]
+            DroppedFile::Web(_) => None,
+            DroppedFile::Name(_) => None,
+        }
+    }
+
+    /// The browser's handle to the dropped file
+    #[cfg(target_arch = "wasm32")]
+    pub fn web_file(&self) -> Option<&web_sys::File> {
+        match &self.file {
+            DroppedFile::Path(_) | DroppedFile::Name(_) => None,
+            DroppedFile::Web(file) => Some(file),
+        }
+    }
+
+    /// Read the whole contents of the file
+    ///
+    /// On web this reads the browser's copy of the file, which is the only way to get at it. A
+    /// browser's file replayed from a [`Recording`] can't be read, because only its name is kept.
+    ///
+    /// [`Recording`]: crate::Recording
+    pub async fn read(&self) -> io::Result<Vec<u8>> {
+        match &self.file {
+            DroppedFile::Path(path) => std::fs::read(path),
+            #[cfg(target_arch = "wasm32")]
+            DroppedFile::Web(file) => {
+                let buffer = wasm_bindgen_futures::JsFuture::from(file.array_buffer())
+                    .await
+                    .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{:?}", err)))?;
+
+                Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+            }
+            DroppedFile::Name(name) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("the contents of {} weren't recorded", name),
+            )),
+        }
+    }
+}
+
+/// Turns the drag and drop events of a canvas into blinds events, because winit doesn't on web
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct DropListener {
+    events: std::rc::Rc<std::cell::RefCell<Vec<super::Event>>>,
+    _listeners: Vec<wasm_bindgen::closure::Closure<dyn FnMut(web_sys::DragEvent)>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+type DragHandler = fn(&web_sys::DragEvent, &mut Vec<super::Event>);
+
+#[cfg(target_arch = "wasm32")]
+impl DropListener {
+    pub fn listen(
+        canvas: &web_sys::HtmlCanvasElement,
+    ) -> Result<DropListener, wasm_bindgen::JsValue> {
+        use super::Event;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let handlers: [(&str, DragHandler); 4] = [
+            ("dragenter", |_, events| {
+                events.push(Event::FileHovered(FileHoveredEvent { path: None }))
+            }),
+            // The browser only allows a drop if dragging over the canvas is cancelled
+            ("dragover", |event, _| event.prevent_default()),
+            ("dragleave", |_, events| {
+                events.push(Event::FileHoverCancelled)
+            }),
+            ("drop", |event, events| {
+                event.prevent_default();
+                let files = event.data_transfer().and_then(|data| data.files());
+                if let Some(files) = files {
+                    for index in 0..files.length() {
+                        if let Some(file) = files.get(index) {
+                            events.push(Event::FileDropped(FileDroppedEvent {
+                                file: DroppedFile::Web(file),
+                            }));
+                        }
+                    }
+                }
+            }),
+        ];
+        let mut listeners = Vec::new();
+        for (kind, handler) in handlers.iter().copied() {
+            let queue = events.clone();
+            let listener = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+                handler(&event, &mut queue.borrow_mut())
+            }) as Box<dyn FnMut(web_sys::DragEvent)>);
+            canvas.add_event_listener_with_callback(kind, listener.as_ref().unchecked_ref())?;
+            listeners.push(listener);
+        }
+
+        Ok(DropListener {
+            events,
+            _listeners: listeners,
+        })
+    }
+
+    /// The events since the last call
+    pub fn take(&self) -> Vec<super::Event> {
+        std::mem::take(&mut *self.events.borrow_mut())
+    }
+}
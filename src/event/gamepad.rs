@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 #[derive(Clone, Debug)]
 /// See [`Event::GamepadConnected`]
 ///
@@ -81,26 +79,9 @@ impl GamepadAxisEvent {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 /// A unique ID for a gamepad that persists after the device is unplugged
-pub struct GamepadId(
-    #[cfg(feature = "gilrs")] pub(crate) gilrs::GamepadId,
-    #[cfg(not(feature = "gilrs"))] usize,
-);
-
-impl PartialOrd for GamepadId {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for GamepadId {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let a: usize = self.0.into();
-        let b: usize = other.0.into();
-        a.cmp(&b)
-    }
-}
+pub struct GamepadId(pub(crate) usize);
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
@@ -151,6 +132,26 @@ pub enum GamepadButton {
     DPadRight,
 }
 
+/// Every [`GamepadButton`], in declaration order
+pub(crate) const ALL_GAMEPAD_BUTTONS: [GamepadButton; 16] = [
+    GamepadButton::Start,
+    GamepadButton::Select,
+    GamepadButton::North,
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightTrigger,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
 /// The stick axes of a gamepad
@@ -162,6 +163,14 @@ pub enum GamepadAxis {
     RightStickY,
 }
 
+/// Every [`GamepadAxis`], in declaration order
+pub(crate) const ALL_GAMEPAD_AXES: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
 #[cfg(feature = "gilrs")]
 pub(crate) fn convert_gilrs_button(event: gilrs::ev::Button) -> Option<GamepadButton> {
     use gilrs::ev::Button::*;
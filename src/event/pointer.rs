@@ -46,6 +46,21 @@ impl PointerMovedEvent {
     }
 }
 
+#[derive(Clone, Debug)]
+/// See [`Event::PointerDelta`]
+///
+/// [`Event::PointerDelta`]: crate::event::Event::PointerDelta
+pub struct PointerDeltaEvent {
+    pub(crate) delta: Vector2<f32>,
+}
+
+impl PointerDeltaEvent {
+    /// How far the mouse moved, in device-specific units that are usually close to pixels
+    pub fn delta(&self) -> Vector2<f32> {
+        self.delta
+    }
+}
+
 #[derive(Clone, Debug)]
 /// See [`Event::PointerInput`]
 ///
@@ -73,7 +88,15 @@ impl PointerInputEvent {
 
 #[derive(Clone, Copy, PartialOrd, PartialEq, Eq, Ord, Debug, Hash)]
 /// A unique ID for multiple mouse pointers
-pub struct PointerId(pub(crate) DeviceId);
+pub struct PointerId(pub(crate) PointerSource);
+
+#[derive(Clone, Copy, PartialOrd, PartialEq, Eq, Ord, Debug, Hash)]
+pub(crate) enum PointerSource {
+    /// A pointer reported by the windowing system
+    Device(DeviceId),
+    /// A pointer that doesn't correspond to a real device, like those from headless input
+    Synthetic(u32),
+}
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 /// A button on a standard 3-button mouse
@@ -5,17 +5,24 @@
 //! to the global state of the input devices. The [`EventCache`] and [`CachedEventStream`] are
 //! designed to make this easy and avoid some non-obvious pitfalls.
 use crate::{
-    Event, EventStream, GamepadAxis, GamepadButton, GamepadId, Key, MouseButton, PointerId,
+    Event, EventStream, Frame, GamepadAxis, GamepadButton, GamepadId, Key, MouseButton, PointerId,
 };
 
 use enum_map::EnumMap;
+use futures_util::ready;
+use futures_util::stream::Stream;
 use mint::Vector2;
 use rustc_hash::FxHashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+const ZERO: Vector2<f32> = Vector2 { x: 0.0, y: 0.0 };
 
 /// A wrapper around [`EventStream`] and [`EventCache`] for convenience
 ///
 /// This is entirely equivalent to using a normal [`EventStream`] and passing all of its events
-/// into an [`EventCache`]
+/// into an [`EventCache`]. Like [`EventStream`], it is also a [`Stream`] of [`Frame`]s; the cache
+/// is updated with every event in a frame before the frame is returned.
 pub struct CachedEventStream {
     events: EventStream,
     cache: EventCache,
@@ -32,8 +39,9 @@ impl CachedEventStream {
     /// See [`EventStream::next_event`]
     pub async fn next_event(&mut self) -> Option<Event> {
         let event = self.events.next_event().await;
-        if let Some(ev) = &event {
-            self.cache.process_event(ev);
+        match &event {
+            Some(ev) => self.cache.process_event(ev),
+            None => self.cache.end_frame(),
         }
 
         event
@@ -44,6 +52,23 @@ impl CachedEventStream {
     }
 }
 
+impl Stream for CachedEventStream {
+    type Item = Frame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Frame>> {
+        let this = &mut *self;
+        let frame = ready!(Pin::new(&mut this.events).poll_next(cx));
+        if let Some(frame) = &frame {
+            for event in frame {
+                this.cache.process_event(event);
+            }
+            this.cache.end_frame();
+        }
+
+        Poll::Ready(frame)
+    }
+}
+
 /// A struct that stores all input event values
 ///
 /// While this is mostly takes care of book keeping necessary to store input event state, it also
@@ -56,12 +81,13 @@ impl CachedEventStream {
 /// [`gamepad`]: EventCache::gamepad
 /// [`key`]: EventCache::key
 /// [`mouse`]: EventCache::mouse
-#[derive(Default)]
 pub struct EventCache {
     keys: EnumMap<Key, bool>,
     global_pointer: PointerState,
     pointers: FxHashMap<PointerId, PointerState>,
     gamepads: FxHashMap<GamepadId, GamepadState>,
+    pending_pointer_delta: Vector2<f32>,
+    pointer_delta: Vector2<f32>,
 }
 
 impl EventCache {
@@ -87,6 +113,10 @@ impl EventCache {
                     .expect("Internal error: pointer failed to exist")
                     .location = ev.location();
             }
+            PointerDelta(ev) => {
+                self.pending_pointer_delta.x += ev.delta().x;
+                self.pending_pointer_delta.y += ev.delta().y;
+            }
             PointerInput(ev) => {
                 let pointer = *ev.pointer();
                 self.ensure_pointer_exists(pointer);
@@ -130,8 +160,19 @@ impl EventCache {
         self.gamepads.insert(id, GamepadState::default());
     }
 
+    /// Mark the end of a frame, so the [`pointer_delta`] covers the frame that just ended
+    ///
+    /// [`CachedEventStream`] calls this when it reaches the end of a frame.
+    ///
+    /// [`pointer_delta`]: EventCache::pointer_delta
+    pub fn end_frame(&mut self) {
+        self.pointer_delta = std::mem::replace(&mut self.pending_pointer_delta, ZERO);
+    }
+
     /// Clear all of the state
     pub fn clear(&mut self) {
+        self.pending_pointer_delta = ZERO;
+        self.pointer_delta = ZERO;
         self.keys.clear();
         self.global_pointer.clear();
         self.pointers.clear();
@@ -151,6 +192,13 @@ impl EventCache {
         &self.global_pointer
     }
 
+    /// The total [`PointerDelta`] of the last frame that ended
+    ///
+    /// [`PointerDelta`]: Event::PointerDelta
+    pub fn pointer_delta(&self) -> Vector2<f32> {
+        self.pointer_delta
+    }
+
     /// The state of the given pointer
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn pointer(&self, id: &PointerId) -> Option<&PointerState> {
@@ -221,6 +269,19 @@ impl PointerState {
     }
 }
 
+impl Default for EventCache {
+    fn default() -> EventCache {
+        EventCache {
+            keys: EnumMap::default(),
+            global_pointer: PointerState::default(),
+            pointers: FxHashMap::default(),
+            gamepads: FxHashMap::default(),
+            pending_pointer_delta: ZERO,
+            pointer_delta: ZERO,
+        }
+    }
+}
+
 impl Default for PointerState {
     fn default() -> PointerState {
         PointerState {
@@ -1,36 +1,68 @@
 use crate::event::Event;
+use crate::WindowId;
 
 use futures_util::future::poll_fn;
+use futures_util::stream::Stream;
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::future::Future;
-use std::sync::Arc;
-use std::task::{Poll, Waker};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 
 /// The source of events for a `blinds` application
 ///
 /// An `EventStream` instance is supplied by [`run`], so creating one is not necessary. Use the
 /// [`next_event`] function to wait for [`Event`]s.
 ///
+/// `EventStream` is also a [`Stream`] of [`Frame`]s, so it can be used with the `StreamExt`
+/// combinators and composed with other async sources. Don't mix the two styles on one stream:
+/// events taken by [`next_event`] won't show up in a [`Frame`].
+///
+/// The stream carries the events of every window in the app; use [`next_tagged_event`] or
+/// [`Frame::tagged_events`] to tell them apart when there are child windows.
+///
 /// [`next_event`]: EventStream::next_event
+/// [`next_tagged_event`]: EventStream::next_tagged_event
 /// [`Event`]: Event
 /// [`run`]: crate::run()
 pub struct EventStream {
-    buffer: Arc<RefCell<EventBuffer>>,
+    buffer: Rc<RefCell<EventBuffer>>,
+    replay: Option<Replay>,
+    frame: Frame,
+}
+
+/// Recorded frames that an [`EventStream`] delivers in place of live events
+struct Replay {
+    current: VecDeque<Event>,
+    frames: VecDeque<Vec<Event>>,
 }
 
 impl EventStream {
     pub(crate) fn new() -> EventStream {
         EventStream {
-            buffer: Arc::new(RefCell::new(EventBuffer {
+            buffer: Rc::new(RefCell::new(EventBuffer {
                 events: VecDeque::new(),
                 waker: None,
                 ready: false,
+                replaying: false,
             })),
+            replay: None,
+            frame: Frame::default(),
         }
     }
 
-    pub(crate) fn buffer(&self) -> Arc<RefCell<EventBuffer>> {
+    pub(crate) fn replaying(self, frames: Vec<Vec<Event>>) -> EventStream {
+        let mut frames: VecDeque<_> = frames.into();
+        let current = frames.pop_front().unwrap_or_default().into();
+        self.buffer.borrow_mut().replaying = true;
+        EventStream {
+            replay: Some(Replay { current, frames }),
+            ..self
+        }
+    }
+
+    pub(crate) fn buffer(&self) -> Rc<RefCell<EventBuffer>> {
         self.buffer.clone()
     }
 
@@ -44,35 +76,154 @@ impl EventStream {
     /// [`Event`]: Event
     pub fn next_event(&mut self) -> impl Future<Output = Option<Event>> + '_ {
         poll_fn(move |cx| {
-            let mut buffer = self.buffer.borrow_mut();
-            match buffer.events.pop_front() {
-                Some(event) => Poll::Ready(Some(event)),
+            self.poll_event(cx)
+                .map(|event| event.map(|(_, event)| event))
+        })
+    }
+
+    /// Like [`next_event`], but also provides the [`WindowId`] of the window the event happened in
+    ///
+    /// Replayed [`Recording`]s don't track windows, so their events are tagged with the main
+    /// window.
+    ///
+    /// [`next_event`]: EventStream::next_event
+    /// [`Recording`]: crate::Recording
+    pub fn next_tagged_event(&mut self) -> impl Future<Output = Option<(WindowId, Event)>> + '_ {
+        poll_fn(move |cx| self.poll_event(cx))
+    }
+
+    fn poll_event(&mut self, cx: &mut Context) -> Poll<Option<(WindowId, Event)>> {
+        if self.replay.is_some() {
+            return self.poll_replay(cx);
+        }
+        let mut buffer = self.buffer.borrow_mut();
+        match buffer.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => {
+                if buffer.ready {
+                    buffer.ready = false;
+                    Poll::Ready(None)
+                } else {
+                    buffer.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Deliver the recorded events, ending each recorded frame when a live frame ends
+    fn poll_replay(&mut self, cx: &mut Context) -> Poll<Option<(WindowId, Event)>> {
+        let replay = self
+            .replay
+            .as_mut()
+            .expect("Internal error: polled a replay without a recording");
+        if let Some(event) = replay.current.pop_front() {
+            return Poll::Ready(Some((WindowId::MAIN, event)));
+        }
+        let mut buffer = self.buffer.borrow_mut();
+        if buffer.ready {
+            // Live input is ignored until the recording runs out
+            buffer.events.clear();
+            buffer.ready = false;
+            match replay.frames.pop_front() {
+                Some(frame) => replay.current = frame.into(),
                 None => {
-                    if buffer.ready {
-                        buffer.ready = false;
-                        Poll::Ready(None)
-                    } else {
-                        buffer.waker = Some(cx.waker().clone());
-                        Poll::Pending
-                    }
+                    buffer.replaying = false;
+                    self.replay = None;
                 }
             }
-        })
+            Poll::Ready(None)
+        } else {
+            buffer.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Frame;
+
+    /// Wait for the end of the next frame; the stream never ends
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Frame>> {
+        loop {
+            match self.poll_event(cx) {
+                Poll::Ready(Some((window, event))) => {
+                    self.frame.windows.push(window);
+                    self.frame.events.push(event);
+                }
+                Poll::Ready(None) => return Poll::Ready(Some(std::mem::take(&mut self.frame))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// All of the events delivered in a single frame, from [`EventStream`]'s [`Stream`] impl
+#[derive(Clone, Debug, Default)]
+pub struct Frame {
+    events: Vec<Event>,
+    windows: Vec<WindowId>,
+}
+
+impl Frame {
+    /// The events, in the order they happened
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// The events along with the [`WindowId`] of the window each happened in
+    pub fn tagged_events(&self) -> impl Iterator<Item = (WindowId, &Event)> {
+        self.windows.iter().copied().zip(self.events.iter())
+    }
+
+    /// If no events happened during the frame
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl IntoIterator for Frame {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Frame {
+    type Item = &'a Event;
+    type IntoIter = std::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
     }
 }
 
 pub(crate) struct EventBuffer {
-    events: VecDeque<Event>,
+    events: VecDeque<(WindowId, Event)>,
     waker: Option<Waker>,
     ready: bool,
+    /// If the stream still has recorded frames to deliver, each of which needs a live frame
+    replaying: bool,
 }
 
 impl EventBuffer {
-    pub fn push(&mut self, event: Event) {
-        self.events.push_back(event);
+    pub fn push(&mut self, window: WindowId, event: Event) {
+        self.events.push_back((window, event));
         self.mark_ready();
     }
 
+    /// If a task is waiting for the next event
+    pub fn has_waiter(&self) -> bool {
+        self.waker.is_some()
+    }
+
+    /// If a replayed recording is waiting on live frames to move forward
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
     pub fn mark_ready(&mut self) {
         if let Some(waker) = self.waker.take() {
             waker.wake();
@@ -0,0 +1,275 @@
+use crate::monitor::all_monitors;
+use crate::{FullscreenMode, Settings, WindowPosition};
+use mint::Vector2;
+use std::cell::RefCell;
+use winit::event_loop::EventLoopWindowTarget;
+use winit::monitor::MonitorHandle;
+use winit::window::Window as WinitWindow;
+
+const HEADER: &str = "blinds-geometry 1";
+
+/// The size and placement of a window, as saved by [`Settings::remember_geometry`]
+///
+/// [`Settings::remember_geometry`]: crate::Settings::remember_geometry
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Geometry {
+    /// The logical size of the window while it was last windowed and not maximized
+    size: Vector2<f32>,
+    /// The physical position of the window while it was last windowed and not maximized
+    position: Option<Vector2<i32>>,
+    /// If the window was maximized, through `Settings::maximized` or `Window::set_maximized`
+    maximized: bool,
+    /// The fullscreen mode, with the monitor left out because it's identified by `monitor`
+    fullscreen: FullscreenMode,
+    /// The position and size of the monitor the window was on
+    monitor: Option<(Vector2<i32>, Vector2<u32>)>,
+}
+
+impl Geometry {
+    pub fn capture(window: &WinitWindow, fullscreen: FullscreenMode, maximized: bool) -> Geometry {
+        let size = window.inner_size().to_logical(window.scale_factor());
+        Geometry {
+            size: Vector2 {
+                x: size.width,
+                y: size.height,
+            },
+            position: window.outer_position().ok().map(|position| Vector2 {
+                x: position.x,
+                y: position.y,
+            }),
+            maximized,
+            fullscreen: match fullscreen {
+                FullscreenMode::Windowed => FullscreenMode::Windowed,
+                FullscreenMode::Borderless(_) => FullscreenMode::Borderless(None),
+                FullscreenMode::Exclusive {
+                    size, refresh_rate, ..
+                } => FullscreenMode::Exclusive {
+                    monitor: None,
+                    size,
+                    refresh_rate,
+                },
+            },
+            monitor: window.current_monitor().as_ref().map(monitor_bounds),
+        }
+    }
+
+    fn write(&self) -> String {
+        let mut text = format!("{}\nsize {} {}\n", HEADER, self.size.x, self.size.y);
+        if let Some(position) = self.position {
+            text += &format!("position {} {}\n", position.x, position.y);
+        }
+        if self.maximized {
+            text += "maximized\n";
+        }
+        text += &match self.fullscreen {
+            FullscreenMode::Windowed => "fullscreen windowed\n".to_owned(),
+            FullscreenMode::Borderless(_) => "fullscreen borderless\n".to_owned(),
+            FullscreenMode::Exclusive {
+                size, refresh_rate, ..
+            } => format!(
+                "fullscreen exclusive {} {} {}\n",
+                size.x, size.y, refresh_rate
+            ),
+        };
+        if let Some((position, size)) = self.monitor {
+            text += &format!(
+                "monitor {} {} {} {}\n",
+                position.x, position.y, size.x, size.y
+            );
+        }
+
+        text
+    }
+
+    fn read(text: &str) -> Option<Geometry> {
+        let mut lines = text.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let mut geometry = Geometry {
+            size: Vector2 { x: 0.0, y: 0.0 },
+            position: None,
+            maximized: false,
+            fullscreen: FullscreenMode::Windowed,
+            monitor: None,
+        };
+        for line in lines {
+            let mut parts = line.split(' ');
+            let kind = parts.next()?;
+            let args: Vec<&str> = parts.collect();
+            let int = |idx: usize| args.get(idx)?.parse::<i32>().ok();
+            let uint = |idx: usize| args.get(idx)?.parse::<u32>().ok();
+            match kind {
+                "size" => {
+                    geometry.size = Vector2 {
+                        x: args.first()?.parse().ok()?,
+                        y: args.get(1)?.parse().ok()?,
+                    }
+                }
+                "position" => {
+                    geometry.position = Some(Vector2 {
+                        x: int(0)?,
+                        y: int(1)?,
+                    })
+                }
+                "maximized" => geometry.maximized = true,
+                "fullscreen" => {
+                    geometry.fullscreen = match *args.first()? {
+                        "windowed" => FullscreenMode::Windowed,
+                        "borderless" => FullscreenMode::Borderless(None),
+                        "exclusive" => FullscreenMode::Exclusive {
+                            monitor: None,
+                            size: Vector2 {
+                                x: uint(1)?,
+                                y: uint(2)?,
+                            },
+                            refresh_rate: args.get(3)?.parse().ok()?,
+                        },
+                        _ => return None,
+                    }
+                }
+                "monitor" => {
+                    let position = Vector2 {
+                        x: int(0)?,
+                        y: int(1)?,
+                    };
+                    let size = Vector2 {
+                        x: uint(2)?,
+                        y: uint(3)?,
+                    };
+                    geometry.monitor = Some((position, size));
+                }
+                _ => return None,
+            }
+        }
+        if geometry.size.x <= 0.0 || geometry.size.y <= 0.0 {
+            return None;
+        }
+
+        Some(geometry)
+    }
+}
+
+fn monitor_bounds(monitor: &MonitorHandle) -> (Vector2<i32>, Vector2<u32>) {
+    let (position, size) = (monitor.position(), monitor.size());
+    (
+        Vector2 {
+            x: position.x,
+            y: position.y,
+        },
+        Vector2 {
+            x: size.width,
+            y: size.height,
+        },
+    )
+}
+
+/// The saved geometry of a window that has [`Settings::remember_geometry`] set
+///
+/// [`Settings::remember_geometry`]: crate::Settings::remember_geometry
+pub(crate) struct Remembered {
+    key: String,
+    geometry: RefCell<Geometry>,
+}
+
+impl Remembered {
+    /// Apply the geometry saved under the settings' key, if any
+    ///
+    /// The position and fullscreen mode are only restored if the monitor the window was on is
+    /// still connected, so the window can't end up off-screen.
+    pub fn restore<T>(
+        el: &EventLoopWindowTarget<T>,
+        settings: &mut Settings,
+    ) -> Option<Remembered> {
+        let key = settings.remember_geometry.clone()?;
+        if let Some(saved) = load(&key).as_deref().and_then(Geometry::read) {
+            settings.size = saved.size;
+            settings.maximized = saved.maximized;
+            let monitor = saved.monitor.and_then(|bounds| {
+                all_monitors(el.available_monitors(), el.primary_monitor())
+                    .iter()
+                    .position(|monitor| monitor_bounds(monitor) == bounds)
+            });
+            if let Some(index) = monitor {
+                if let Some(position) = saved.position {
+                    settings.position = WindowPosition::At(position);
+                }
+                settings.fullscreen = match saved.fullscreen {
+                    FullscreenMode::Windowed => FullscreenMode::Windowed,
+                    FullscreenMode::Borderless(_) => FullscreenMode::Borderless(Some(index)),
+                    FullscreenMode::Exclusive {
+                        size, refresh_rate, ..
+                    } => FullscreenMode::Exclusive {
+                        monitor: Some(index),
+                        size,
+                        refresh_rate,
+                    },
+                };
+            }
+        }
+
+        Some(Remembered {
+            key,
+            geometry: RefCell::new(Geometry {
+                size: settings.size,
+                position: match settings.position {
+                    WindowPosition::At(position) => Some(position),
+                    WindowPosition::Auto | WindowPosition::Centered(_) => None,
+                },
+                maximized: settings.maximized,
+                fullscreen: FullscreenMode::Windowed,
+                monitor: None,
+            }),
+        })
+    }
+
+    /// Track the window's latest geometry, saving it right away on web
+    pub fn update(&self, mut current: Geometry) {
+        let mut geometry = self.geometry.borrow_mut();
+        if current.fullscreen.is_fullscreen() || current.maximized {
+            // Keep the windowed size and position, to return to when leaving fullscreen or
+            // unmaximizing
+            current.size = geometry.size;
+            current.position = geometry.position;
+        }
+        if *geometry != current {
+            *geometry = current;
+            // There's no reliable time to save when a page closes, so save every change
+            #[cfg(target_arch = "wasm32")]
+            store(&self.key, &geometry.write());
+        }
+    }
+
+    pub fn save(&self) {
+        store(&self.key, &self.geometry.borrow().write());
+    }
+}
+
+// Failing to read or write the geometry isn't worth stopping the app for, so errors are ignored
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load(key: &str) -> Option<String> {
+    std::fs::read_to_string(key).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn store(key: &str, text: &str) {
+    let _ = std::fs::write(key, text);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn store(key: &str, text: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, text);
+    }
+}
@@ -0,0 +1,348 @@
+use crate::event::*;
+use crate::{CursorGrab, CursorIcon, FullscreenMode, Icon, Settings, WindowPosition};
+use mint::Vector2;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// A scripted source of events for [`run_headless`]
+///
+/// Events are grouped into frames: everything added before a call to [`end_frame`] is delivered
+/// together, followed by the `None` that [`EventStream::next_event`] uses to mark the end of a
+/// frame.
+///
+/// ```
+/// use blinds::{HeadlessInput, Key};
+///
+/// let mut input = HeadlessInput::new();
+/// input.key(Key::Space, true).end_frame();
+/// input.key(Key::Space, false).end_frame();
+/// ```
+///
+/// [`run_headless`]: crate::run_headless
+/// [`end_frame`]: HeadlessInput::end_frame
+/// [`EventStream::next_event`]: crate::EventStream::next_event
+#[derive(Clone, Default)]
+pub struct HeadlessInput {
+    frames: VecDeque<Vec<Event>>,
+    current: Vec<Event>,
+}
+
+impl HeadlessInput {
+    pub fn new() -> HeadlessInput {
+        HeadlessInput::default()
+    }
+
+    /// Finish the current frame, so further events are delivered in the next one
+    pub fn end_frame(&mut self) -> &mut Self {
+        self.frames.push_back(std::mem::take(&mut self.current));
+        self
+    }
+
+    /// Add a number of frames with no events in them
+    pub fn idle_frames(&mut self, count: usize) -> &mut Self {
+        for _ in 0..count {
+            self.end_frame();
+        }
+        self
+    }
+
+    /// Resize the window to the given logical size
+    pub fn resize(&mut self, size: Vector2<f32>) -> &mut Self {
+        self.push(Event::Resized(ResizedEvent { size }))
+    }
+
+    /// Change the scale factor of the window
+    pub fn scale_factor(&mut self, scale: f32) -> &mut Self {
+        self.push(Event::ScaleFactorChanged(ScaleFactorChangedEvent { scale }))
+    }
+
+    pub fn focus(&mut self, focus: bool) -> &mut Self {
+        self.push(Event::FocusChanged(FocusChangedEvent { focus }))
+    }
+
+    pub fn character(&mut self, chr: char) -> &mut Self {
+        self.push(Event::ReceivedCharacter(ReceivedCharacterEvent { chr }))
+    }
+
+    pub fn key(&mut self, key: Key, is_down: bool) -> &mut Self {
+        self.push(Event::KeyboardInput(KeyboardEvent { key, is_down }))
+    }
+
+    pub fn modifiers(&mut self, shift: bool, ctrl: bool, alt: bool, logo: bool) -> &mut Self {
+        self.push(Event::ModifiersChanged(ModifiersChangedEvent {
+            shift,
+            ctrl,
+            alt,
+            logo,
+        }))
+    }
+
+    /// Headless input has a single pointer, which enters the window
+    pub fn pointer_entered(&mut self) -> &mut Self {
+        self.push(Event::PointerEntered(PointerEnteredEvent(HEADLESS_POINTER)))
+    }
+
+    pub fn pointer_left(&mut self) -> &mut Self {
+        self.push(Event::PointerLeft(PointerLeftEvent(HEADLESS_POINTER)))
+    }
+
+    /// Move the pointer to a logical location relative to the top-left of the window
+    pub fn pointer_moved(&mut self, location: Vector2<f32>) -> &mut Self {
+        self.push(Event::PointerMoved(PointerMovedEvent {
+            id: HEADLESS_POINTER,
+            location,
+        }))
+    }
+
+    /// Move the mouse by some amount, as raw device motion
+    pub fn pointer_delta(&mut self, delta: Vector2<f32>) -> &mut Self {
+        self.push(Event::PointerDelta(PointerDeltaEvent { delta }))
+    }
+
+    pub fn pointer_input(&mut self, button: MouseButton, is_down: bool) -> &mut Self {
+        self.push(Event::PointerInput(PointerInputEvent {
+            id: HEADLESS_POINTER,
+            button,
+            is_down,
+        }))
+    }
+
+    pub fn scroll(&mut self, delta: ScrollDelta) -> &mut Self {
+        self.push(Event::ScrollInput(delta))
+    }
+
+    /// Move the window to a position in physical pixels, as if the user dragged it
+    pub fn moved(&mut self, position: Vector2<i32>) -> &mut Self {
+        self.push(Event::Moved(MovedEvent { position }))
+    }
+
+    /// Paste some text into the window, as a browser would
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        self.push(Event::Paste(text.to_owned()))
+    }
+
+    /// Drag a file over the window
+    pub fn file_hovered(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.push(Event::FileHovered(FileHoveredEvent {
+            path: Some(path.into()),
+        }))
+    }
+
+    /// Drop a file onto the window, which the app can read from `path`
+    pub fn file_dropped(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.push(Event::FileDropped(FileDroppedEvent {
+            file: DroppedFile::Path(path.into()),
+        }))
+    }
+
+    pub fn file_hover_cancelled(&mut self) -> &mut Self {
+        self.push(Event::FileHoverCancelled)
+    }
+
+    /// Ask to close the window, as if the user clicked its close button
+    pub fn close_requested(&mut self) -> &mut Self {
+        self.push(Event::CloseRequested)
+    }
+
+    /// Connect a gamepad, identified by an arbitrary index
+    pub fn gamepad_connected(&mut self, gamepad: usize) -> &mut Self {
+        self.push(Event::GamepadConnected(GamepadConnectedEvent(GamepadId(
+            gamepad,
+        ))))
+    }
+
+    pub fn gamepad_disconnected(&mut self, gamepad: usize) -> &mut Self {
+        self.push(Event::GamepadDisconnected(GamepadDisconnectedEvent(
+            GamepadId(gamepad),
+        )))
+    }
+
+    pub fn gamepad_button(
+        &mut self,
+        gamepad: usize,
+        button: GamepadButton,
+        is_down: bool,
+    ) -> &mut Self {
+        self.push(Event::GamepadButton(GamepadButtonEvent {
+            id: GamepadId(gamepad),
+            button,
+            is_down,
+            is_repeat: false,
+        }))
+    }
+
+    pub fn gamepad_axis(&mut self, gamepad: usize, axis: GamepadAxis, value: f32) -> &mut Self {
+        self.push(Event::GamepadAxis(GamepadAxisEvent {
+            id: GamepadId(gamepad),
+            axis,
+            value,
+        }))
+    }
+
+    pub(crate) fn push(&mut self, event: Event) -> &mut Self {
+        self.current.push(event);
+        self
+    }
+
+    pub(crate) fn into_frames(mut self) -> VecDeque<Vec<Event>> {
+        if !self.current.is_empty() {
+            self.end_frame();
+        }
+        self.frames
+    }
+}
+
+const HEADLESS_POINTER: PointerId = PointerId(PointerSource::Synthetic(0));
+
+/// The in-memory stand-in for a window when running headless
+///
+/// Calls to [`Window`] methods are recorded here instead of going to a real window. When
+/// [`run_headless`] completes it returns the final state, so tests can check what the app did.
+///
+/// [`Window`]: crate::Window
+/// [`run_headless`]: crate::run_headless
+#[derive(Clone)]
+pub struct HeadlessWindow {
+    size: Cell<Vector2<f32>>,
+    position: Cell<Vector2<i32>>,
+    scale: Cell<f32>,
+    title: RefCell<String>,
+    icon: RefCell<Option<Icon>>,
+    visible: Cell<bool>,
+    #[cfg(feature = "clipboard")]
+    clipboard: RefCell<Option<String>>,
+    fullscreen: Cell<FullscreenMode>,
+    cursor_icon: RefCell<Option<CursorIcon>>,
+    cursor_grab: Cell<CursorGrab>,
+}
+
+impl HeadlessWindow {
+    pub(crate) fn new(settings: &Settings) -> HeadlessWindow {
+        HeadlessWindow {
+            size: Cell::new(settings.size),
+            position: Cell::new(match settings.position {
+                WindowPosition::At(position) => position,
+                // There are no monitors to place the window on
+                WindowPosition::Auto | WindowPosition::Centered(_) => Vector2 { x: 0, y: 0 },
+            }),
+            scale: Cell::new(1.0),
+            title: RefCell::new(settings.title.clone()),
+            icon: RefCell::new(settings.icon.clone()),
+            visible: Cell::new(settings.shown_at_start()),
+            #[cfg(feature = "clipboard")]
+            clipboard: RefCell::new(None),
+            fullscreen: Cell::new(settings.fullscreen),
+            cursor_icon: RefCell::new(settings.cursor_icon.clone()),
+            cursor_grab: Cell::new(CursorGrab::None),
+        }
+    }
+
+    /// Apply the changes an event would make to a real window
+    pub(crate) fn process_event(&self, event: &Event) {
+        match event {
+            Event::Resized(ev) => self.size.set(ev.logical_size()),
+            Event::Moved(ev) => self.position.set(ev.position()),
+            Event::ScaleFactorChanged(ev) => self.scale.set(ev.scale_factor()),
+            _ => (),
+        }
+    }
+
+    /// The logical size of the window
+    pub fn size(&self) -> Vector2<f32> {
+        self.size.get()
+    }
+
+    pub(crate) fn set_size(&self, size: Vector2<f32>) {
+        self.size.set(size);
+    }
+
+    /// The position of the window in physical pixels
+    pub fn position(&self) -> Vector2<i32> {
+        self.position.get()
+    }
+
+    pub(crate) fn set_position(&self, position: Vector2<i32>) {
+        self.position.set(position);
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale.get()
+    }
+
+    /// The last title the window was given
+    pub fn title(&self) -> String {
+        self.title.borrow().clone()
+    }
+
+    pub(crate) fn set_title(&self, title: &str) {
+        self.title.replace(title.to_owned());
+    }
+
+    /// The last icon the window was given with [`Settings::icon`] or [`Window::set_icon`]
+    ///
+    /// [`Settings::icon`]: crate::Settings::icon
+    /// [`Window::set_icon`]: crate::Window::set_icon
+    pub fn icon(&self) -> Option<Icon> {
+        self.icon.borrow().clone()
+    }
+
+    pub(crate) fn set_icon(&self, icon: Option<Icon>) {
+        self.icon.replace(icon);
+    }
+
+    /// If the window is shown, see [`Window::show`] and [`Window::hide`]
+    ///
+    /// [`Window::show`]: crate::Window::show
+    /// [`Window::hide`]: crate::Window::hide
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    pub(crate) fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+    }
+
+    /// The text last given to [`Window::set_clipboard_text`], which headless windows keep to
+    /// themselves instead of using the system clipboard
+    ///
+    /// [`Window::set_clipboard_text`]: crate::Window::set_clipboard_text
+    #[cfg(feature = "clipboard")]
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.clipboard.borrow().clone()
+    }
+
+    #[cfg(feature = "clipboard")]
+    pub(crate) fn set_clipboard_text(&self, text: &str) {
+        self.clipboard.replace(Some(text.to_owned()));
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen.get().is_fullscreen()
+    }
+
+    /// The last fullscreen mode the window was given
+    pub fn fullscreen(&self) -> FullscreenMode {
+        self.fullscreen.get()
+    }
+
+    pub(crate) fn set_fullscreen(&self, fullscreen: FullscreenMode) {
+        self.fullscreen.set(fullscreen);
+    }
+
+    pub fn cursor_icon(&self) -> Option<CursorIcon> {
+        self.cursor_icon.borrow().clone()
+    }
+
+    pub(crate) fn set_cursor_icon(&self, icon: Option<CursorIcon>) {
+        self.cursor_icon.replace(icon);
+    }
+
+    pub fn cursor_grab(&self) -> CursorGrab {
+        self.cursor_grab.get()
+    }
+
+    pub(crate) fn set_cursor_grab(&self, grab: CursorGrab) {
+        self.cursor_grab.set(grab);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::Error;
+use mint::Vector2;
+
+/// An image for the window's icon or the page's favicon, see [`Settings::icon`]
+///
+/// An icon can hold the same picture at several resolutions, combined with [`join`]. On web
+/// each one becomes a favicon for the browser to choose from; desktop windows only take a single
+/// image, so they use the largest.
+///
+/// [`Settings::icon`]: crate::Settings::icon
+/// [`join`]: Icon::join
+#[derive(Clone, PartialEq, Eq)]
+pub struct Icon {
+    images: Vec<IconImage>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct IconImage {
+    rgba: Vec<u8>,
+    size: Vector2<u32>,
+}
+
+impl Icon {
+    /// Create an icon from 8-bit RGBA pixels, in rows from the top left
+    ///
+    /// It fails if `rgba` isn't `width * height * 4` bytes.
+    pub fn from_rgba(rgba: Vec<u8>, size: Vector2<u32>) -> Result<Icon, Error> {
+        if size.x == 0 || size.y == 0 || rgba.len() != size.x as usize * size.y as usize * 4 {
+            return Err(Error::IconLoad(
+                format!(
+                    "expected {} bytes of RGBA data for a {}x{} image, got {}",
+                    size.x as usize * size.y as usize * 4,
+                    size.x,
+                    size.y,
+                    rgba.len()
+                )
+                .into(),
+            ));
+        }
+
+        Ok(Icon {
+            images: vec![IconImage { rgba, size }],
+        })
+    }
+
+    /// Load an icon from an image file
+    ///
+    /// Browsers can't read files, so on web use [`from_memory`] with the fetched bytes instead.
+    ///
+    /// [`from_memory`]: Icon::from_memory
+    #[cfg(all(feature = "image", not(target_arch = "wasm32")))]
+    pub fn from_path(path: &str) -> Result<Icon, Error> {
+        let (rgba, size) = load_path(path).map_err(|err| Error::IconLoad(Box::new(err)))?;
+        Icon::from_rgba(rgba, size)
+    }
+
+    /// Load an icon from the contents of an image file, in any format `image` was built with
+    #[cfg(feature = "image")]
+    pub fn from_memory(bytes: &[u8]) -> Result<Icon, Error> {
+        let (rgba, size) = load_memory(bytes).map_err(|err| Error::IconLoad(Box::new(err)))?;
+        Icon::from_rgba(rgba, size)
+    }
+
+    /// Combine the resolutions of two icons into one
+    pub fn join(mut self, other: Icon) -> Icon {
+        self.images.extend(other.images);
+
+        self
+    }
+
+    /// The resolutions of the icon, in pixels
+    pub fn sizes(&self) -> Vec<Vector2<u32>> {
+        self.images.iter().map(|image| image.size).collect()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn to_winit(&self) -> Option<winit::window::Icon> {
+        let largest = self
+            .images
+            .iter()
+            .max_by_key(|image| image.size.x as u64 * image.size.y as u64)?;
+
+        winit::window::Icon::from_rgba(largest.rgba.clone(), largest.size.x, largest.size.y).ok()
+    }
+
+    /// The favicon links for the icon, as `href` and `sizes` pairs
+    #[cfg(all(feature = "favicon", target_arch = "wasm32"))]
+    pub(crate) fn links(&self) -> Vec<(String, Option<String>)> {
+        self.images
+            .iter()
+            .filter_map(|image| {
+                let href = data_url(&image.rgba, image.size)?;
+                Some((href, Some(format!("{}x{}", image.size.x, image.size.y))))
+            })
+            .collect()
+    }
+}
+
+/// Decode an image file into 8-bit RGBA pixels and their size, for icons and cursors
+#[cfg(all(feature = "image", not(target_arch = "wasm32")))]
+pub(crate) fn load_path(path: &str) -> Result<(Vec<u8>, Vector2<u32>), image::ImageError> {
+    image::open(path).map(into_rgba)
+}
+
+/// Decode the contents of an image file into 8-bit RGBA pixels and their size
+#[cfg(feature = "image")]
+pub(crate) fn load_memory(bytes: &[u8]) -> Result<(Vec<u8>, Vector2<u32>), image::ImageError> {
+    image::load_from_memory(bytes).map(into_rgba)
+}
+
+#[cfg(feature = "image")]
+fn into_rgba(img: image::DynamicImage) -> (Vec<u8>, Vector2<u32>) {
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    (
+        rgba.into_raw(),
+        Vector2 {
+            x: width,
+            y: height,
+        },
+    )
+}
+
+/// Encode RGBA pixels as a PNG `data:` URL, by drawing them to an offscreen canvas
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn data_url(rgba: &[u8], size: Vector2<u32>) -> Option<String> {
+    use wasm_bindgen::{Clamped, JsCast};
+    let document = web_sys::window()?.document()?;
+    let canvas: web_sys::HtmlCanvasElement =
+        document.create_element("canvas").ok()?.dyn_into().ok()?;
+    canvas.set_width(size.x);
+    canvas.set_height(size.y);
+    let context: web_sys::CanvasRenderingContext2d =
+        canvas.get_context("2d").ok()??.dyn_into().ok()?;
+    let data =
+        web_sys::ImageData::new_with_u8_clamped_array_and_sh(Clamped(rgba), size.x, size.y).ok()?;
+    context.put_image_data(&data, 0.0, 0.0).ok()?;
+
+    canvas.to_data_url().ok()
+}
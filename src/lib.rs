@@ -16,26 +16,54 @@
 //! ```
 //!
 //! The core of blinds is [`run`], which executes your app and provides your [`Window`] and
-//! [`EventStream`] instances.
+//! [`EventStream`] instances. To test an app without a display, [`run_headless`] drives it with
+//! scripted input instead.
 //!
 //! [`run`]: run()
+//! [`run_headless`]: run_headless()
 //! [`Window`]: Window
 //! [`EventStream`]: EventStream
+mod child;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod cursor;
+mod error;
 mod event_stream;
+mod geometry;
+mod headless;
+mod icon;
+mod monitor;
 mod run;
 mod settings;
+mod spawn;
+mod timer;
+mod user_event;
 mod window;
 
 pub mod event;
 #[cfg(feature = "event-cache")]
 pub mod event_cache;
+pub mod record;
 
+pub use self::child::WindowId;
+pub use self::cursor::CustomCursor;
+pub use self::error::Error;
 pub use self::event::{Event, GamepadAxis, GamepadButton, GamepadId, Key, MouseButton, PointerId};
 #[cfg(feature = "event-cache")]
 pub use self::event_cache::{CachedEventStream, EventCache};
-pub use self::event_stream::EventStream;
-pub use self::run::run;
-pub use self::settings::{CursorIcon, Settings};
+pub use self::event_stream::{EventStream, Frame};
+pub use self::headless::{HeadlessInput, HeadlessWindow};
+pub use self::icon::Icon;
+pub use self::monitor::{Monitor, VideoMode};
+pub use self::record::{Recorder, Recording};
+pub use self::run::{run, run_headless, run_with_user_events, try_run};
+pub use self::settings::{
+    ContextConfig, CursorGrab, CursorIcon, FullscreenMode, GlConfig, GlProfile, GlVersion,
+    PixelFormat, PowerMode, Settings, WebGlAttributes, WindowPosition,
+};
+pub use self::spawn::{spawn_local, JoinHandle};
+pub use self::timer::{interval, sleep, timeout, Interval, Sleep, TimedOut};
+pub use self::user_event::{UserEventProxy, UserEvents};
 pub use self::window::Window;
 
 pub(crate) use self::event_stream::EventBuffer;
@@ -0,0 +1,95 @@
+use mint::Vector2;
+use winit::monitor::{MonitorHandle, VideoMode as WinitVideoMode};
+
+/// A display connected to the computer, see [`Window::monitors`]
+///
+/// Positions and sizes are in physical pixels, because monitors with different scale factors
+/// don't share a logical coordinate space. On web there is a single placeholder monitor, which
+/// has no name, and a position and size of zero.
+///
+/// [`Window::monitors`]: crate::Window::monitors
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Monitor(pub(crate) MonitorHandle);
+
+impl Monitor {
+    /// The human-readable name of the monitor, if the platform provides one
+    pub fn name(&self) -> Option<String> {
+        self.0.name()
+    }
+
+    /// The top-left corner of the monitor on the desktop, in physical pixels
+    pub fn position(&self) -> Vector2<i32> {
+        let position = self.0.position();
+        Vector2 {
+            x: position.x,
+            y: position.y,
+        }
+    }
+
+    /// The resolution of the monitor, in physical pixels
+    pub fn size(&self) -> Vector2<u32> {
+        let size = self.0.size();
+        Vector2 {
+            x: size.width,
+            y: size.height,
+        }
+    }
+
+    /// The DPI scale factor of the monitor, see [`Window::scale_factor`]
+    ///
+    /// [`Window::scale_factor`]: crate::Window::scale_factor
+    pub fn scale_factor(&self) -> f32 {
+        self.0.scale_factor() as f32
+    }
+
+    /// The video modes the monitor supports in exclusive fullscreen
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        self.0.video_modes().map(VideoMode).collect()
+    }
+}
+
+/// The monitors winit lists, or just the primary monitor when it can't list them
+///
+/// On web winit lists no monitors, but still has a placeholder primary monitor, which gives
+/// monitor indices like `FullscreenMode::Borderless(Some(0))` something to refer to.
+pub(crate) fn all_monitors(
+    available: impl Iterator<Item = MonitorHandle>,
+    primary: Option<MonitorHandle>,
+) -> Vec<MonitorHandle> {
+    let monitors: Vec<_> = available.collect();
+    if monitors.is_empty() {
+        primary.into_iter().collect()
+    } else {
+        monitors
+    }
+}
+
+/// A resolution, bit depth and refresh rate that a [`Monitor`] supports
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VideoMode(pub(crate) WinitVideoMode);
+
+impl VideoMode {
+    /// The resolution of the mode, in physical pixels
+    pub fn size(&self) -> Vector2<u32> {
+        let size = self.0.size();
+        Vector2 {
+            x: size.width,
+            y: size.height,
+        }
+    }
+
+    /// The number of bits per pixel
+    pub fn bit_depth(&self) -> u16 {
+        self.0.bit_depth()
+    }
+
+    /// The refresh rate of the mode, in hertz
+    pub fn refresh_rate(&self) -> u16 {
+        self.0.refresh_rate()
+    }
+
+    /// The monitor that supports this mode
+    pub fn monitor(&self) -> Monitor {
+        Monitor(self.0.monitor())
+    }
+}
@@ -0,0 +1,551 @@
+//! Record the events of a session and replay them later
+//!
+//! Input bugs are often hard to reproduce by hand. A [`Recorder`] wraps an [`EventStream`] and
+//! writes every [`Event`] and frame boundary it delivers, and a [`Recording`] reads them back and
+//! produces an [`EventStream`] that delivers exactly the same events in exactly the same frames.
+//!
+//! Recordings are line-based text, starting with a version header. Pointer IDs are replaced with
+//! stable indices, in the order the pointers were first seen.
+//!
+//! ```no_run
+//! use blinds::{EventStream, Recorder, Recording, Window};
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! async fn record(_window: Window, events: EventStream) {
+//!     let file = File::create("session.txt").unwrap();
+//!     let mut events = Recorder::new(events, file).unwrap();
+//!     loop {
+//!         while let Some(_ev) = events.next_event().await {}
+//!     }
+//! }
+//!
+//! async fn replay(_window: Window, events: EventStream) {
+//!     let file = File::open("session.txt").unwrap();
+//!     let recording = Recording::read(BufReader::new(file)).unwrap();
+//!     let mut events = recording.replay(events);
+//!     loop {
+//!         while let Some(_ev) = events.next_event().await {}
+//!     }
+//! }
+//! ```
+use crate::event::*;
+use crate::{EventStream, FullscreenMode, HeadlessInput};
+use mint::Vector2;
+use std::io::{self, BufRead, Write};
+
+const HEADER: &str = "blinds-recording";
+const VERSION: u32 = 1;
+
+/// A wrapper around [`EventStream`] that writes every event to a recording
+///
+/// If writing fails, recording stops but events are still passed through; the failure is
+/// reported by [`error`] and [`finish`].
+///
+/// [`error`]: Recorder::error
+/// [`finish`]: Recorder::finish
+pub struct Recorder<W: Write> {
+    events: EventStream,
+    out: W,
+    pointers: Vec<PointerId>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Start a recording, writing the header to `out` immediately
+    pub fn new(events: EventStream, mut out: W) -> io::Result<Recorder<W>> {
+        writeln!(out, "{} {}", HEADER, VERSION)?;
+        Ok(Recorder {
+            events,
+            out,
+            pointers: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// See [`EventStream::next_event`]
+    pub async fn next_event(&mut self) -> Option<Event> {
+        let event = self.events.next_event().await;
+        if self.error.is_none() {
+            let result = match &event {
+                Some(ev) => self.write_event(ev),
+                None => writeln!(self.out, "frame").and_then(|_| self.out.flush()),
+            };
+            self.error = result.err();
+        }
+
+        event
+    }
+
+    /// The error that stopped the recording, if any
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Flush and return the output, or the error that stopped the recording
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn pointer_index(&mut self, id: &PointerId) -> usize {
+        match self.pointers.iter().position(|p| p == id) {
+            Some(index) => index,
+            None => {
+                self.pointers.push(*id);
+                self.pointers.len() - 1
+            }
+        }
+    }
+
+    /// Write text as code points, like characters, so it can hold any whitespace
+    fn write_text(&mut self, kind: &str, text: &str) -> io::Result<()> {
+        write!(self.out, "{}", kind)?;
+        for chr in text.chars() {
+            write!(self.out, " {}", chr as u32)?;
+        }
+        writeln!(self.out)
+    }
+
+    fn write_event(&mut self, event: &Event) -> io::Result<()> {
+        use Event::*;
+        match event {
+            Resized(ev) => {
+                let size = ev.logical_size();
+                writeln!(self.out, "resized {} {}", size.x, size.y)
+            }
+            Moved(ev) => {
+                let position = ev.position();
+                writeln!(self.out, "moved {} {}", position.x, position.y)
+            }
+            ScaleFactorChanged(ev) => writeln!(self.out, "scale {}", ev.scale_factor()),
+            FocusChanged(ev) => writeln!(self.out, "focus {}", ev.is_focused() as u8),
+            ReceivedCharacter(ev) => writeln!(self.out, "char {}", ev.character() as u32),
+            KeyboardInput(ev) => writeln!(self.out, "key {:?} {}", ev.key(), ev.is_down() as u8),
+            PointerEntered(ev) => {
+                let id = self.pointer_index(ev.pointer());
+                writeln!(self.out, "pointer-entered {}", id)
+            }
+            PointerLeft(ev) => {
+                let id = self.pointer_index(ev.pointer());
+                writeln!(self.out, "pointer-left {}", id)
+            }
+            PointerMoved(ev) => {
+                let id = self.pointer_index(ev.pointer());
+                let location = ev.location();
+                writeln!(
+                    self.out,
+                    "pointer-moved {} {} {}",
+                    id, location.x, location.y
+                )
+            }
+            PointerDelta(ev) => {
+                let delta = ev.delta();
+                writeln!(self.out, "pointer-delta {} {}", delta.x, delta.y)
+            }
+            PointerInput(ev) => {
+                let id = self.pointer_index(ev.pointer());
+                let button = match ev.button() {
+                    MouseButton::Other(idx) => format!("Other:{}", idx),
+                    button => format!("{:?}", button),
+                };
+                writeln!(
+                    self.out,
+                    "pointer-input {} {} {}",
+                    id,
+                    button,
+                    ev.is_down() as u8
+                )
+            }
+            ScrollInput(ScrollDelta::Lines(delta)) => {
+                writeln!(self.out, "scroll-lines {} {}", delta.x, delta.y)
+            }
+            ScrollInput(ScrollDelta::Pixels(delta)) => {
+                writeln!(self.out, "scroll-pixels {} {}", delta.x, delta.y)
+            }
+            ModifiersChanged(ev) => writeln!(
+                self.out,
+                "modifiers {} {} {} {}",
+                ev.shift() as u8,
+                ev.ctrl() as u8,
+                ev.alt() as u8,
+                ev.logo() as u8
+            ),
+            Paste(text) => self.write_text("paste", text),
+            FileHovered(ev) => match ev.path() {
+                Some(path) => self.write_text("file-hovered path", &path.to_string_lossy()),
+                None => writeln!(self.out, "file-hovered none"),
+            },
+            // Files dropped into a browser have no path, so only their name is kept
+            FileDropped(ev) => match ev.path() {
+                Some(path) => self.write_text("file-dropped path", &path.to_string_lossy()),
+                None => self.write_text("file-dropped name", &ev.name()),
+            },
+            FileHoverCancelled => writeln!(self.out, "file-hover-cancelled"),
+            GamepadConnected(ev) => writeln!(self.out, "gamepad-connected {}", ev.gamepad().0),
+            GamepadDisconnected(ev) => {
+                writeln!(self.out, "gamepad-disconnected {}", ev.gamepad().0)
+            }
+            GamepadButton(ev) => writeln!(
+                self.out,
+                "gamepad-button {} {:?} {} {}",
+                ev.gamepad().0,
+                ev.button(),
+                ev.is_down() as u8,
+                ev.is_repeat() as u8
+            ),
+            GamepadAxis(ev) => writeln!(
+                self.out,
+                "gamepad-axis {} {:?} {}",
+                ev.gamepad().0,
+                ev.axis(),
+                ev.value()
+            ),
+            CloseRequested => writeln!(self.out, "close-requested"),
+            FullscreenChanged(ev) => {
+                let monitor = |index: Option<usize>| match index {
+                    Some(index) => index.to_string(),
+                    None => "-".to_owned(),
+                };
+                match ev.mode() {
+                    FullscreenMode::Windowed => writeln!(self.out, "fullscreen windowed"),
+                    FullscreenMode::Borderless(index) => {
+                        writeln!(self.out, "fullscreen borderless {}", monitor(index))
+                    }
+                    FullscreenMode::Exclusive {
+                        monitor: index,
+                        size,
+                        refresh_rate,
+                    } => writeln!(
+                        self.out,
+                        "fullscreen exclusive {} {} {} {}",
+                        monitor(index),
+                        size.x,
+                        size.y,
+                        refresh_rate
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// A recorded session, read back from the output of a [`Recorder`]
+#[derive(Clone)]
+pub struct Recording {
+    frames: Vec<Vec<Event>>,
+}
+
+impl Recording {
+    /// Parse a recording, failing with [`io::ErrorKind::InvalidData`] if it is malformed
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Recording> {
+        let mut lines = reader.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        match header.split_once(' ') {
+            Some((HEADER, version)) if version == VERSION.to_string() => (),
+            Some((HEADER, version)) => {
+                return Err(invalid(format!(
+                    "unsupported recording version {}",
+                    version
+                )))
+            }
+            _ => return Err(invalid("missing recording header")),
+        }
+
+        let mut frames = Vec::new();
+        let mut current = Vec::new();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "frame" {
+                frames.push(std::mem::take(&mut current));
+            } else {
+                let event = parse_event(line)
+                    .ok_or_else(|| invalid(format!("bad event on line {}", number + 2)))?;
+                current.push(event);
+            }
+        }
+        if !current.is_empty() {
+            frames.push(current);
+        }
+
+        Ok(Recording { frames })
+    }
+
+    /// The number of frames in the recording
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Replace the events from `events` with the recorded ones
+    ///
+    /// Each recorded frame ends when a live frame ends, so the app sees the same frame splits as
+    /// it did while recording. Live events are discarded until the recording runs out, after
+    /// which the stream delivers live events again.
+    pub fn replay(self, events: EventStream) -> EventStream {
+        events.replaying(self.frames)
+    }
+}
+
+impl From<Recording> for HeadlessInput {
+    fn from(recording: Recording) -> HeadlessInput {
+        let mut input = HeadlessInput::new();
+        for frame in recording.frames {
+            for event in frame {
+                input.push(event);
+            }
+            input.end_frame();
+        }
+
+        input
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn parse_event(line: &str) -> Option<Event> {
+    let mut parts = line.split(' ');
+    let kind = parts.next()?;
+    let args: Vec<&str> = parts.collect();
+    let float = |idx: usize| args.get(idx)?.parse::<f32>().ok();
+    let flag = |idx: usize| match *args.get(idx)? {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    };
+    let vector = |idx: usize| {
+        Some(Vector2 {
+            x: float(idx)?,
+            y: float(idx + 1)?,
+        })
+    };
+    let pointer = |idx: usize| {
+        let index = args.get(idx)?.parse().ok()?;
+        Some(PointerId(PointerSource::Synthetic(index)))
+    };
+    let gamepad = |idx: usize| Some(GamepadId(args.get(idx)?.parse().ok()?));
+    let monitor = |idx: usize| match *args.get(idx)? {
+        "-" => Some(None),
+        index => Some(Some(index.parse().ok()?)),
+    };
+    let number = |idx: usize| args.get(idx)?.parse().ok();
+    let text = |idx: usize| {
+        args.get(idx..)?
+            .iter()
+            .map(|code| std::char::from_u32(code.parse().ok()?))
+            .collect::<Option<String>>()
+    };
+
+    Some(match kind {
+        "resized" => Event::Resized(ResizedEvent { size: vector(0)? }),
+        "moved" => Event::Moved(MovedEvent {
+            position: Vector2 {
+                x: args.first()?.parse().ok()?,
+                y: args.get(1)?.parse().ok()?,
+            },
+        }),
+        "scale" => Event::ScaleFactorChanged(ScaleFactorChangedEvent { scale: float(0)? }),
+        "focus" => Event::FocusChanged(FocusChangedEvent { focus: flag(0)? }),
+        "char" => Event::ReceivedCharacter(ReceivedCharacterEvent {
+            chr: std::char::from_u32(args.first()?.parse().ok()?)?,
+        }),
+        "key" => Event::KeyboardInput(KeyboardEvent {
+            key: find_by_name(&ALL_KEYS, args.first()?)?,
+            is_down: flag(1)?,
+        }),
+        "pointer-entered" => Event::PointerEntered(PointerEnteredEvent(pointer(0)?)),
+        "pointer-left" => Event::PointerLeft(PointerLeftEvent(pointer(0)?)),
+        "pointer-moved" => Event::PointerMoved(PointerMovedEvent {
+            id: pointer(0)?,
+            location: vector(1)?,
+        }),
+        "pointer-delta" => Event::PointerDelta(PointerDeltaEvent { delta: vector(0)? }),
+        "pointer-input" => Event::PointerInput(PointerInputEvent {
+            id: pointer(0)?,
+            button: match *args.get(1)? {
+                "Left" => MouseButton::Left,
+                "Right" => MouseButton::Right,
+                "Middle" => MouseButton::Middle,
+                other => MouseButton::Other(other.strip_prefix("Other:")?.parse().ok()?),
+            },
+            is_down: flag(2)?,
+        }),
+        "scroll-lines" => Event::ScrollInput(ScrollDelta::Lines(vector(0)?)),
+        "scroll-pixels" => Event::ScrollInput(ScrollDelta::Pixels(vector(0)?)),
+        "modifiers" => Event::ModifiersChanged(ModifiersChangedEvent {
+            shift: flag(0)?,
+            ctrl: flag(1)?,
+            alt: flag(2)?,
+            logo: flag(3)?,
+        }),
+        "paste" => Event::Paste(text(0)?),
+        "file-hovered" => Event::FileHovered(FileHoveredEvent {
+            path: match *args.first()? {
+                "none" if args.len() == 1 => None,
+                "path" => Some(text(1)?.into()),
+                _ => return None,
+            },
+        }),
+        "file-dropped" => Event::FileDropped(FileDroppedEvent {
+            file: match *args.first()? {
+                "path" => DroppedFile::Path(text(1)?.into()),
+                "name" => DroppedFile::Name(text(1)?),
+                _ => return None,
+            },
+        }),
+        "file-hover-cancelled" => Event::FileHoverCancelled,
+        "gamepad-connected" => Event::GamepadConnected(GamepadConnectedEvent(gamepad(0)?)),
+        "gamepad-disconnected" => Event::GamepadDisconnected(GamepadDisconnectedEvent(gamepad(0)?)),
+        "gamepad-button" => Event::GamepadButton(GamepadButtonEvent {
+            id: gamepad(0)?,
+            button: find_by_name(&ALL_GAMEPAD_BUTTONS, args.get(1)?)?,
+            is_down: flag(2)?,
+            is_repeat: flag(3)?,
+        }),
+        "gamepad-axis" => Event::GamepadAxis(GamepadAxisEvent {
+            id: gamepad(0)?,
+            axis: find_by_name(&ALL_GAMEPAD_AXES, args.get(1)?)?,
+            value: float(2)?,
+        }),
+        "close-requested" => Event::CloseRequested,
+        "fullscreen" => Event::FullscreenChanged(FullscreenChangedEvent {
+            mode: match *args.first()? {
+                "windowed" => FullscreenMode::Windowed,
+                "borderless" => FullscreenMode::Borderless(monitor(1)?),
+                "exclusive" => FullscreenMode::Exclusive {
+                    monitor: monitor(1)?,
+                    size: Vector2 {
+                        x: number(2)?,
+                        y: number(3)?,
+                    },
+                    refresh_rate: args.get(4)?.parse().ok()?,
+                },
+                _ => return None,
+            },
+        }),
+        _ => return None,
+    })
+}
+
+/// Find the value whose `Debug` name matches, which is how they are written to recordings
+fn find_by_name<T: Copy + std::fmt::Debug>(values: &[T], name: &str) -> Option<T> {
+    values
+        .iter()
+        .copied()
+        .find(|value| format!("{:?}", value) == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(events: Vec<Event>) -> Vec<Event> {
+        let mut recorder = Recorder::new(EventStream::new(), Vec::new()).unwrap();
+        for event in &events {
+            recorder.write_event(event).unwrap();
+        }
+        let text = recorder.finish().unwrap();
+        let recording = Recording::read(&text[..]).unwrap();
+
+        recording.frames.into_iter().flatten().collect()
+    }
+
+    #[test]
+    fn every_event_round_trips() {
+        let pointer = PointerId(PointerSource::Synthetic(0));
+        let events = vec![
+            Event::Resized(ResizedEvent {
+                size: Vector2 { x: 640.5, y: 480.0 },
+            }),
+            Event::Moved(MovedEvent {
+                position: Vector2 { x: -20, y: 35 },
+            }),
+            Event::ScaleFactorChanged(ScaleFactorChangedEvent { scale: 1.25 }),
+            Event::FocusChanged(FocusChangedEvent { focus: true }),
+            Event::ReceivedCharacter(ReceivedCharacterEvent { chr: ' ' }),
+            Event::KeyboardInput(KeyboardEvent {
+                key: Key::Space,
+                is_down: true,
+            }),
+            Event::PointerEntered(PointerEnteredEvent(pointer)),
+            Event::PointerLeft(PointerLeftEvent(pointer)),
+            Event::PointerMoved(PointerMovedEvent {
+                id: pointer,
+                location: Vector2 { x: 1.5, y: -2.0 },
+            }),
+            Event::PointerDelta(PointerDeltaEvent {
+                delta: Vector2 { x: 0.1, y: 3.0 },
+            }),
+            Event::PointerInput(PointerInputEvent {
+                id: pointer,
+                button: MouseButton::Other(7),
+                is_down: false,
+            }),
+            Event::ScrollInput(ScrollDelta::Lines(Vector2 { x: 0.0, y: -1.0 })),
+            Event::ScrollInput(ScrollDelta::Pixels(Vector2 { x: 4.0, y: 12.5 })),
+            Event::ModifiersChanged(ModifiersChangedEvent {
+                shift: true,
+                ctrl: false,
+                alt: true,
+                logo: false,
+            }),
+            Event::Paste("two\nlines ".to_owned()),
+            Event::GamepadConnected(GamepadConnectedEvent(GamepadId(3))),
+            Event::GamepadDisconnected(GamepadDisconnectedEvent(GamepadId(3))),
+            Event::GamepadButton(GamepadButtonEvent {
+                id: GamepadId(3),
+                button: GamepadButton::South,
+                is_down: true,
+                is_repeat: true,
+            }),
+            Event::GamepadAxis(GamepadAxisEvent {
+                id: GamepadId(3),
+                axis: GamepadAxis::RightStickY,
+                value: -0.75,
+            }),
+            Event::CloseRequested,
+            Event::FullscreenChanged(FullscreenChangedEvent {
+                mode: FullscreenMode::Windowed,
+            }),
+            Event::FullscreenChanged(FullscreenChangedEvent {
+                mode: FullscreenMode::Borderless(None),
+            }),
+            Event::FullscreenChanged(FullscreenChangedEvent {
+                mode: FullscreenMode::Exclusive {
+                    monitor: Some(1),
+                    size: Vector2 { x: 1920, y: 1080 },
+                    refresh_rate: 60,
+                },
+            }),
+            Event::FileHovered(FileHoveredEvent {
+                path: Some("a dir/file.txt".into()),
+            }),
+            Event::FileHovered(FileHoveredEvent {
+                path: Some("".into()),
+            }),
+            Event::FileHovered(FileHoveredEvent { path: None }),
+            Event::FileDropped(FileDroppedEvent {
+                file: DroppedFile::Path("a dir/file.txt".into()),
+            }),
+            Event::FileDropped(FileDroppedEvent {
+                file: DroppedFile::Name("file.txt".to_owned()),
+            }),
+            Event::FileHoverCancelled,
+        ];
+
+        let replayed = round_trip(events.clone());
+        assert_eq!(format!("{:?}", replayed), format!("{:?}", events));
+    }
+}
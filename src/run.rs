@@ -1,10 +1,18 @@
 use crate::event::*;
-use crate::{EventBuffer, EventStream, Settings, Window, WindowContents};
+#[cfg(feature = "gilrs")]
+use crate::WindowId;
+use crate::{spawn, timer};
+use crate::{
+    Error, EventBuffer, EventStream, HeadlessInput, HeadlessWindow, PowerMode, Settings,
+    UserEvents, Window, WindowContents,
+};
 use futures_executor::LocalPool;
-use futures_util::task::LocalSpawnExt;
-use std::cell::RefCell;
+use instant::Instant;
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
 use std::future::Future;
-use std::sync::Arc;
+use std::rc::Rc;
+use std::time::Duration;
 use winit::event::Event as WinitEvent;
 use winit::event_loop::{ControlFlow, EventLoop};
 
@@ -14,9 +22,32 @@ use winit::event_loop::{ControlFlow, EventLoop};
 /// desktop and web. It is a single-threaded executor, because wasm doesn't support multithreading
 /// at the moment.
 ///
-/// Currently blinds only supports one window, and `settings` determines how it will be
-/// constructed.
+/// `settings` determines how the main window will be constructed; more windows can be opened
+/// with [`Window::open_child`]. The app exits when the main window closes.
+///
+/// If the window can't be created, `run` panics; use [`try_run`] to handle the error instead.
+///
+/// [`try_run`]: try_run()
+/// [`Window::open_child`]: crate::Window::open_child
 pub fn run<F, T>(settings: Settings, app: F) -> !
+where
+    T: 'static + Future<Output = ()>,
+    F: 'static + FnOnce(Window, EventStream) -> T,
+{
+    match try_run(settings, app) {
+        Ok(never) => match never {},
+        Err(err) => panic!("Failed to start the app: {}", err),
+    }
+}
+
+/// Like [`run`], but returns an [`Error`] if the window can't be created
+///
+/// This only returns if startup fails, for example because the window icon couldn't be loaded or
+/// no GL context matched the settings. The app can then show a fallback message, or call
+/// `try_run` again with safer [`Settings`].
+///
+/// [`run`]: run()
+pub fn try_run<F, T>(settings: Settings, app: F) -> Result<Infallible, Error>
 where
     T: 'static + Future<Output = ()>,
     F: 'static + FnOnce(Window, EventStream) -> T,
@@ -25,66 +56,346 @@ where
     let buffer = stream.buffer();
 
     let event_loop = EventLoop::new();
-    let window = Arc::new(WindowContents::new(&event_loop, settings));
+    let window = Rc::new(WindowContents::new(&event_loop, settings)?);
     let pool = LocalPool::new();
-    pool.spawner()
-        .spawn_local(app(Window(window.clone()), stream))
-        .expect("Failed to start application");
+    let finished = spawn::start_app(&pool, app(Window(window.clone()), stream));
+
+    do_run(event_loop, window, pool, buffer, finished, |()| ())
+}
+
+/// Like [`run`], but with a channel for custom events that can be sent from other threads
+///
+/// The app receives a [`UserEvents`] alongside the [`EventStream`]; call [`UserEvents::proxy`]
+/// to get a cloneable [`UserEventProxy`] to hand to worker threads. Each event sent through a
+/// proxy wakes the event loop and is delivered to the [`UserEvents`] in order.
+///
+/// ```no_run
+/// use blinds::{run_with_user_events, EventStream, Settings, UserEvents, Window};
+///
+/// run_with_user_events(Settings::default(), app);
+///
+/// async fn app(_window: Window, _events: EventStream, mut loaded: UserEvents<Vec<u8>>) {
+///     let proxy = loaded.proxy();
+///     std::thread::spawn(move || {
+///         let _ = proxy.send(std::fs::read("level.dat").unwrap_or_default());
+///     });
+///     let level = loaded.next_user_event().await;
+///     println!("Loaded {} bytes", level.len());
+/// }
+/// ```
+///
+/// [`run`]: run()
+/// [`UserEventProxy`]: crate::UserEventProxy
+pub fn run_with_user_events<U, F, T>(settings: Settings, app: F) -> !
+where
+    U: 'static,
+    T: 'static + Future<Output = ()>,
+    F: 'static + FnOnce(Window, EventStream, UserEvents<U>) -> T,
+{
+    let stream = EventStream::new();
+    let buffer = stream.buffer();
+
+    let event_loop = EventLoop::with_user_event();
+    let user_events = UserEvents::new(event_loop.create_proxy());
+    let user_buffer = user_events.buffer();
+    let window = match WindowContents::new(&event_loop, settings) {
+        Ok(window) => Rc::new(window),
+        Err(err) => panic!("Failed to start the app: {}", err),
+    };
+    let pool = LocalPool::new();
+    let finished = spawn::start_app(&pool, app(Window(window.clone()), stream, user_events));
+
+    do_run(event_loop, window, pool, buffer, finished, move |event| {
+        user_buffer.borrow_mut().push(event)
+    })
+}
+
+/// Run a blinds application without a window or GL context
+///
+/// This drives `app` on the same executor as [`run`], but instead of the operating system or
+/// browser, events come from the frames scripted in `input`. The [`Window`] given to the app is
+/// a stand-in that tracks changes in memory; its final state is returned once the app completes,
+/// the window is closed, or `input` runs out of frames, whichever comes first. If the input runs
+/// out first, the app future is dropped, along with any tasks it started with [`spawn_local`].
+///
+/// This is intended for testing app logic on machines without a display or GPU.
+///
+/// ```
+/// use blinds::{run_headless, EventStream, HeadlessInput, Settings, Window};
+///
+/// let mut input = HeadlessInput::new();
+/// input.character('a').end_frame();
+///
+/// let window = run_headless(Settings::default(), input, app);
+/// assert_eq!(window.title(), "a");
+///
+/// async fn app(window: Window, mut events: EventStream) {
+///     while let Some(blinds::Event::ReceivedCharacter(ev)) = events.next_event().await {
+///         window.set_title(&ev.character().to_string());
+///     }
+/// }
+/// ```
+///
+/// [`run`]: run()
+/// [`spawn_local`]: crate::spawn_local()
+pub fn run_headless<F, T>(settings: Settings, input: HeadlessInput, app: F) -> HeadlessWindow
+where
+    T: 'static + Future<Output = ()>,
+    F: 'static + FnOnce(Window, EventStream) -> T,
+{
+    let stream = EventStream::new();
+    let buffer = stream.buffer();
+
+    let window = Rc::new(WindowContents::headless(&settings));
+    let mut pool = LocalPool::new();
+    let finished = spawn::start_app(&pool, app(Window(window.clone()), stream));
 
-    do_run(event_loop, window, pool, buffer)
+    let headless = window
+        .headless_window()
+        .expect("Internal error: headless run without a headless window");
+    pool.run_until_stalled();
+    for frame in input.into_frames() {
+        #[cfg(not(target_arch = "wasm32"))]
+        wait_for_timers(&mut pool, &buffer, &finished);
+        if finished.get() || window.is_closed() {
+            break;
+        }
+        for event in frame {
+            if let Event::CloseRequested = event {
+                window.close_requested();
+            }
+            headless.process_event(&event);
+            buffer.borrow_mut().push(window.id(), event);
+        }
+        poll_window_events(&window, &buffer);
+        buffer.borrow_mut().mark_ready();
+        timer::wake_expired();
+        pool.run_until_stalled();
+    }
+    spawn::finish_app();
+    timer::clear();
+
+    headless.clone()
 }
 
-fn do_run(
-    event_loop: EventLoop<()>,
-    window: Arc<WindowContents>,
+fn do_run<U: 'static>(
+    event_loop: EventLoop<U>,
+    window: Rc<WindowContents>,
     mut pool: LocalPool,
-    buffer: Arc<RefCell<EventBuffer>>,
+    buffer: Rc<RefCell<EventBuffer>>,
+    finished: Rc<Cell<bool>>,
+    mut user_event: impl 'static + FnMut(U),
 ) -> ! {
     #[cfg(feature = "gilrs")]
     let mut gilrs = gilrs::Gilrs::new();
 
-    let mut finished = pool.try_run_one();
+    pool.run_until_stalled();
+    let mut next_frame = Instant::now();
+    // If the loop woke up because a `ControlFlow::WaitUntil` deadline passed
+    let mut timed_out = false;
 
-    event_loop.run(move |event, _, ctrl| {
+    event_loop.run(move |event, target, ctrl| {
+        if let WinitEvent::LoopDestroyed = &event {
+            // Children that were closed already saved their geometry
+            for window in open_windows(&window) {
+                window.save_geometry();
+            }
+        }
         match event {
             WinitEvent::NewEvents(winit::event::StartCause::Init) => {
                 *ctrl = ControlFlow::Poll;
             }
-            WinitEvent::WindowEvent { event, .. } => {
+            WinitEvent::NewEvents(cause) => {
+                timed_out = matches!(cause, winit::event::StartCause::ResumeTimeReached { .. });
+            }
+            WinitEvent::WindowEvent { window_id, event } => {
+                let contents = match find_window(&window, window_id) {
+                    Some(contents) => contents,
+                    // Closed child windows don't deliver events anymore
+                    None => return,
+                };
                 if let winit::event::WindowEvent::CloseRequested = &event {
-                    *ctrl = ControlFlow::Exit;
+                    contents.close_requested();
                 }
                 if let winit::event::WindowEvent::Resized(size) = &event {
-                    window.resize(*size);
+                    contents.resize(*size);
+                    contents.keep_aspect_ratio(*size);
+                }
+                if let winit::event::WindowEvent::Focused(focused) = &event {
+                    contents.set_focused(*focused);
                 }
-                if let Some(event) = window_event(event, &window) {
-                    buffer.borrow_mut().push(event);
+                if let Some(event) = window_event(event, &contents) {
+                    buffer.borrow_mut().push(contents.id(), event);
                 }
             }
+            WinitEvent::DeviceEvent { event, .. } => {
+                // Raw device events arrive even when the app isn't focused
+                let focused = open_windows(&window)
+                    .into_iter()
+                    .find(|window| window.is_focused());
+                if let (Some(focused), Some(event)) = (focused, device_event(event)) {
+                    buffer.borrow_mut().push(focused.id(), event);
+                }
+            }
+            WinitEvent::UserEvent(event) => user_event(event),
             WinitEvent::LoopDestroyed | WinitEvent::MainEventsCleared => {
-                buffer.borrow_mut().mark_ready();
+                if let PowerMode::FrameCap(fps) = window.power_mode() {
+                    let now = Instant::now();
+                    if now < next_frame {
+                        // Too early for another frame, so leave the events buffered until then
+                        *ctrl = ControlFlow::WaitUntil(next_frame);
+                        return;
+                    }
+                    let period = Duration::from_secs(1) / fps.max(1);
+                    next_frame += period;
+                    if next_frame <= now {
+                        next_frame = now + period;
+                    }
+                }
+                poll_window_events(&window, &buffer);
+                let expired = timer::wake_expired();
+                // Waking up only to check the gamepads isn't a frame, unless they had input
+                if !timed_out || expired || window.power_mode() != PowerMode::Wait {
+                    buffer.borrow_mut().mark_ready();
+                }
+                #[cfg(feature = "gilrs")]
+                process_gilrs_events(&mut gilrs, &buffer, window.id());
+                pool.run_until_stalled();
+                while window.open_children(target) {
+                    // Let the app pick up the windows it asked for within the same frame
+                    pool.run_until_stalled();
+                }
                 #[cfg(feature = "gilrs")]
-                process_gilrs_events(&mut gilrs, &buffer);
-                finished = pool.try_run_one();
+                let gamepads = matches!(&gilrs, Ok(gilrs) if gilrs.gamepads().next().is_some());
+                #[cfg(not(feature = "gilrs"))]
+                let gamepads = false;
+                *ctrl = control_flow(window.power_mode(), next_frame, gamepads, &buffer.borrow());
             }
             _ => (),
         }
-        if finished {
+        if finished.get() || window.is_closed() {
+            // Any tasks that are still running are dropped along with the pool
             *ctrl = ControlFlow::Exit;
         }
     })
 }
 
+/// The open window that winit knows by `id`, whether it's the main window or a child
+fn find_window(
+    main: &Rc<WindowContents>,
+    id: winit::window::WindowId,
+) -> Option<Rc<WindowContents>> {
+    if main.is_native(id) {
+        return Some(main.clone());
+    }
+    main.registry()
+        .children()
+        .into_iter()
+        .find(|child| child.is_native(id) && !child.is_closed())
+}
+
+/// Queue the events that blinds detects by checking the windows, rather than getting from winit
+fn poll_window_events(main: &Rc<WindowContents>, buffer: &Rc<RefCell<EventBuffer>>) {
+    for window in open_windows(main) {
+        for event in window.poll_events() {
+            buffer.borrow_mut().push(window.id(), event);
+        }
+        window.update_geometry();
+        window.hold_cursor();
+    }
+}
+
+/// The main window and the child windows that haven't been closed
+fn open_windows(main: &Rc<WindowContents>) -> Vec<Rc<WindowContents>> {
+    let mut windows = vec![main.clone()];
+    windows.extend(main.registry().children());
+    windows.retain(|window| !window.is_closed());
+
+    windows
+}
+
+/// How long `PowerMode::Wait` sleeps at most while gamepads are connected
+///
+/// gilrs can't wake the event loop, so its events are only picked up when a frame runs.
+const GAMEPAD_POLL: Duration = Duration::from_millis(16);
+
+/// Decide when the event loop should next run the app, based on the power mode and timers
+fn control_flow(
+    mode: PowerMode,
+    next_frame: Instant,
+    gamepads: bool,
+    buffer: &EventBuffer,
+) -> ControlFlow {
+    let timer = timer::next_deadline();
+    match mode {
+        PowerMode::Poll => match timer {
+            // Nothing is waiting for a frame, so sleep until a timer is due
+            Some(deadline) if !buffer.has_waiter() => ControlFlow::WaitUntil(deadline),
+            _ => ControlFlow::Poll,
+        },
+        // Each recorded frame needs a live one, so waiting for input would stall the replay
+        PowerMode::Wait if buffer.is_replaying() => ControlFlow::Poll,
+        PowerMode::Wait if gamepads => {
+            let poll = Instant::now() + GAMEPAD_POLL;
+            ControlFlow::WaitUntil(timer.map_or(poll, |deadline| deadline.min(poll)))
+        }
+        PowerMode::Wait => match timer {
+            Some(deadline) => ControlFlow::WaitUntil(deadline),
+            None => ControlFlow::Wait,
+        },
+        PowerMode::FrameCap(_) => ControlFlow::WaitUntil(next_frame),
+    }
+}
+
+/// Block while the app is only waiting on timers, like `ControlFlow::WaitUntil` does in `run`
+///
+/// Blocking the thread isn't possible on web, so there timers just advance with the frames.
+#[cfg(not(target_arch = "wasm32"))]
+fn wait_for_timers(pool: &mut LocalPool, buffer: &Rc<RefCell<EventBuffer>>, finished: &Cell<bool>) {
+    while !finished.get() && !buffer.borrow().has_waiter() {
+        let deadline = match timer::next_deadline() {
+            Some(deadline) => deadline,
+            None => break,
+        };
+        std::thread::sleep(deadline.saturating_duration_since(instant::Instant::now()));
+        timer::wake_expired();
+        pool.run_until_stalled();
+    }
+}
+
 #[cfg(feature = "gilrs")]
 fn process_gilrs_events(
     gilrs: &mut Result<gilrs::Gilrs, gilrs::Error>,
-    buffer: &Arc<RefCell<EventBuffer>>,
+    buffer: &Rc<RefCell<EventBuffer>>,
+    main: WindowId,
 ) {
     if let Ok(gilrs) = gilrs.as_mut() {
         while let Some(ev) = gilrs.next_event() {
             if let Some(ev) = gamepad_event(ev) {
-                buffer.borrow_mut().push(ev);
+                buffer.borrow_mut().push(main, ev);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_executor::block_on;
+
+    #[test]
+    fn wait_mode_polls_while_replaying() {
+        let recorded = vec![vec![Event::CloseRequested], vec![Event::FileHoverCancelled]];
+        let mut events = EventStream::new().replaying(recorded);
+        let buffer = events.buffer();
+        let flow = || control_flow(PowerMode::Wait, Instant::now(), false, &buffer.borrow());
+
+        for _ in 0..2 {
+            assert_eq!(flow(), ControlFlow::Poll);
+            buffer.borrow_mut().mark_ready();
+            assert!(block_on(events.next_event()).is_some());
+            assert!(block_on(events.next_event()).is_none());
+        }
+        assert_eq!(flow(), ControlFlow::Wait);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{CustomCursor, Icon};
 use mint::Vector2;
 
 /// The various options to pass to the Window and/or GL context
@@ -7,27 +8,143 @@ pub struct Settings {
     pub size: Vector2<f32>,
     /// If the cursor should be visible over the application, or if the cursor should be hidden
     pub cursor_icon: Option<CursorIcon>,
-    /// If the application should be fullscreen
-    pub fullscreen: bool,
+    /// If the application should be fullscreen, and on which monitor
+    ///
+    /// Defaults to [`FullscreenMode::Windowed`]
+    pub fullscreen: FullscreenMode,
     /// The icon on the window or the favicon on the tab
+    ///
+    /// Takes the place of `icon_path` when both are set. See [`Window::set_icon`] to change it
+    /// later.
+    ///
+    /// [`Window::set_icon`]: crate::Window::set_icon
+    pub icon: Option<Icon>,
+    /// The path of an image file for the icon on the window or the favicon on the tab
     #[cfg(feature = "image")]
     pub icon_path: Option<&'static str>,
     /// How many samples to do for MSAA
     ///
     /// By default it is None; if it is Some, it should be a non-zero power of two
     ///
-    /// Does nothing on web currently
+    /// On web, any value turns on the WebGL `antialias` attribute
     pub multisampling: Option<u16>,
+    /// The OpenGL version, profile and framebuffer to request
+    ///
+    /// On web, only the depth and stencil bits apply: a non-zero count requests that buffer.
+    pub gl: GlConfig,
+    /// The WebGL context attributes that have no desktop equivalent
+    ///
+    /// Does nothing on desktop
+    pub webgl: WebGlAttributes,
     /// Enable or disable vertical sync
     ///
     /// Does nothing on web; defaults to true
     pub vsync: bool,
+    /// Where the window is placed on the desktop
+    ///
+    /// Defaults to [`WindowPosition::Auto`]
+    pub position: WindowPosition,
+    /// Save the window's size, position, fullscreen mode and maximization under this name
+    ///
+    /// On desktop the name is the path of a small file, like one in the app's config directory,
+    /// written when the app exits or the window is closed. On web it is a `localStorage` key,
+    /// written whenever the geometry changes. The saved values take the place of `size`,
+    /// `position`, `fullscreen` and `maximized` on the next run, but the position and fullscreen
+    /// mode are only used if the monitor the window was on is still connected. Maximization is
+    /// saved as it was last set through `maximized` or [`Window::set_maximized`], because the
+    /// platform can't report it.
+    ///
+    /// Defaults to None, which doesn't save anything. Headless windows never save.
+    ///
+    /// [`Window::set_maximized`]: crate::Window::set_maximized
+    pub remember_geometry: Option<String>,
     /// If the window can be resized by the user
     ///
     /// Does nothing on web; defaults to false
     pub resizable: bool,
+    /// The smallest logical size the user can resize the window to
+    ///
+    /// Does nothing on web; defaults to None
+    pub min_size: Option<Vector2<f32>>,
+    /// The largest logical size the user can resize the window to
+    ///
+    /// Does nothing on web; defaults to None
+    pub max_size: Option<Vector2<f32>>,
+    /// Keep the window's width divided by its height at this ratio when the user resizes it
+    ///
+    /// The platforms don't support this directly, so blinds corrects the size after each resize,
+    /// following whichever side the user changed more. The window can briefly show the wrong
+    /// shape while being dragged.
+    ///
+    /// Does nothing on web, in fullscreen, or while maximized; defaults to None
+    pub aspect_ratio: Option<f32>,
+    /// If the window has a title bar and borders
+    ///
+    /// Does nothing on web; defaults to true
+    pub decorations: bool,
+    /// If the window stays above other windows
+    ///
+    /// Does nothing on web; defaults to false
+    pub always_on_top: bool,
+    /// If the window starts maximized
+    ///
+    /// Does nothing on web; defaults to false
+    pub maximized: bool,
+    /// If the window is shown when it's created
+    ///
+    /// On web a hidden canvas keeps its place in the page. Defaults to true; see
+    /// [`Window::show`] to show it later.
+    ///
+    /// [`Window::show`]: crate::Window::show
+    pub visible: bool,
+    /// Keep the window hidden until the first [`Window::present`], so it never shows a frame
+    /// that hasn't been drawn
+    ///
+    /// Calling [`Window::show`] or [`Window::hide`] before then takes over from this. Does
+    /// nothing if `visible` is false; defaults to false
+    ///
+    /// [`Window::present`]: crate::Window::present
+    /// [`Window::show`]: crate::Window::show
+    /// [`Window::hide`]: crate::Window::hide
+    pub show_after_present: bool,
+    /// If the parts of the window that are drawn with alpha show what's behind it
+    ///
+    /// This also asks for at least 8 alpha bits in the framebuffer, and turns on
+    /// [`WebGlAttributes::alpha`] on web. Whether the desktop shows through depends on the
+    /// platform and compositor. Defaults to false
+    pub transparent: bool,
     /// The title of your application
-    pub title: &'static str,
+    pub title: String,
+    /// How often the event loop runs when nothing is happening
+    ///
+    /// Defaults to [`PowerMode::Poll`]; see [`Window::set_power_mode`] to change it later.
+    ///
+    /// [`Window::set_power_mode`]: crate::Window::set_power_mode
+    pub power_mode: PowerMode,
+    /// If the app should exit as soon as the user asks to close the window
+    ///
+    /// When false, [`Event::CloseRequested`] is delivered and the app decides when to exit with
+    /// [`Window::close`]. Defaults to true.
+    ///
+    /// [`Event::CloseRequested`]: crate::Event::CloseRequested
+    /// [`Window::close`]: crate::Window::close
+    pub exit_on_close: bool,
+    /// If creating the GL context fails, retry with progressively safer options
+    ///
+    /// The options are degraded in order: multisampling is dropped, then vsync, then the sRGB
+    /// framebuffer. Use [`Window::context_config`] to find out which options were obtained.
+    ///
+    /// Does nothing on web; defaults to false
+    ///
+    /// [`Window::context_config`]: crate::Window::context_config
+    pub context_fallback: bool,
+}
+
+impl Settings {
+    /// If the window should be shown as soon as it's created
+    pub(crate) fn shown_at_start(&self) -> bool {
+        self.visible && !self.show_after_present
+    }
 }
 
 impl Default for Settings {
@@ -38,20 +155,267 @@ impl Default for Settings {
                 y: 768.0,
             },
             cursor_icon: Some(CursorIcon::Default),
-            fullscreen: false,
+            fullscreen: FullscreenMode::Windowed,
+            icon: None,
             #[cfg(feature = "image")]
             icon_path: None,
             multisampling: None,
+            gl: GlConfig::default(),
+            webgl: WebGlAttributes::default(),
             vsync: true,
+            position: WindowPosition::Auto,
+            remember_geometry: None,
             resizable: false,
-            title: "",
+            min_size: None,
+            max_size: None,
+            aspect_ratio: None,
+            decorations: true,
+            always_on_top: false,
+            maximized: false,
+            visible: true,
+            show_after_present: false,
+            transparent: false,
+            title: String::new(),
+            power_mode: PowerMode::Poll,
+            exit_on_close: true,
+            context_fallback: false,
         }
     }
 }
 
+/// Where to place the window on the desktop, see [`Settings::position`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WindowPosition {
+    /// Let the operating system decide
+    Auto,
+    /// Put the top-left corner of the window at a position in physical pixels
+    ///
+    /// This uses the same coordinates as [`Window::position`] and [`Monitor::position`].
+    ///
+    /// [`Window::position`]: crate::Window::position
+    /// [`Monitor::position`]: crate::Monitor::position
+    At(Vector2<i32>),
+    /// Center the window on a monitor, by its index in [`Window::monitors`]
+    ///
+    /// None means the primary monitor.
+    ///
+    /// [`Window::monitors`]: crate::Window::monitors
+    Centered(Option<usize>),
+}
+
+/// How the window covers the screen, see [`Settings::fullscreen`]
+///
+/// Monitors are chosen by their index in [`Window::monitors`]; None means the monitor the window
+/// is on, or the primary monitor when the window is created.
+///
+/// [`Window::monitors`]: crate::Window::monitors
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FullscreenMode {
+    /// A regular window
+    Windowed,
+    /// A borderless window covering a whole monitor, without changing its video mode
+    Borderless(Option<usize>),
+    /// Exclusive fullscreen, switching the monitor to one of its [`VideoMode`]s
+    ///
+    /// If the monitor doesn't support a mode with this size and refresh rate, the window is made
+    /// borderless instead. Browsers don't allow changing video modes, so on web this is always
+    /// borderless.
+    ///
+    /// [`VideoMode`]: crate::VideoMode
+    Exclusive {
+        monitor: Option<usize>,
+        /// The resolution in physical pixels, see [`VideoMode::size`]
+        ///
+        /// [`VideoMode::size`]: crate::VideoMode::size
+        size: Vector2<u32>,
+        /// The refresh rate in hertz, see [`VideoMode::refresh_rate`]
+        ///
+        /// [`VideoMode::refresh_rate`]: crate::VideoMode::refresh_rate
+        refresh_rate: u16,
+    },
+}
+
+impl FullscreenMode {
+    /// If the window covers a whole monitor
+    pub fn is_fullscreen(self) -> bool {
+        self != FullscreenMode::Windowed
+    }
+}
+
+/// `true` is borderless fullscreen on the current monitor, and `false` is windowed
+impl From<bool> for FullscreenMode {
+    fn from(fullscreen: bool) -> FullscreenMode {
+        if fullscreen {
+            FullscreenMode::Borderless(None)
+        } else {
+            FullscreenMode::Windowed
+        }
+    }
+}
+
+/// The details of the OpenGL context to request, part of the [`Settings`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlConfig {
+    /// Which API and version to request; defaults to [`GlVersion::Latest`]
+    pub version: GlVersion,
+    /// Which profile to request, if any; defaults to None, which lets the platform pick
+    ///
+    /// Only applies to OpenGL 3.2 and newer
+    pub profile: Option<GlProfile>,
+    /// The minimum number of bits in the depth buffer; defaults to 24
+    pub depth_bits: u8,
+    /// The minimum number of bits in the stencil buffer; defaults to 8
+    pub stencil_bits: u8,
+    /// The minimum number of alpha bits in the color buffer; defaults to 8
+    ///
+    /// Raised to 8 when [`Settings::transparent`] is set
+    pub alpha_bits: u8,
+    /// If the framebuffer should be sRGB-capable; defaults to true
+    pub srgb: bool,
+    /// If the context should be created with debugging enabled; defaults to false
+    pub debug: bool,
+}
+
+impl Default for GlConfig {
+    fn default() -> GlConfig {
+        GlConfig {
+            version: GlVersion::Latest,
+            profile: None,
+            depth_bits: 24,
+            stencil_bits: 8,
+            alpha_bits: 8,
+            srgb: true,
+            debug: false,
+        }
+    }
+}
+
+/// The web-only attributes of the WebGL context, part of the [`Settings`]
+///
+/// These are used by both [`Window::webgl_context`] and [`Window::webgl2_context`], along with
+/// [`Settings::multisampling`] and the depth and stencil bits of [`Settings::gl`]. Those follow
+/// the desktop defaults rather than the browser's, so the context isn't antialiased unless
+/// multisampling is requested, and has a stencil buffer unless `stencil_bits` is 0.
+///
+/// [`Window::webgl_context`]: crate::Window::webgl_context
+/// [`Window::webgl2_context`]: crate::Window::webgl2_context
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WebGlAttributes {
+    /// If the canvas has an alpha channel to blend with the page; defaults to false
+    pub alpha: bool,
+    /// If the colors drawn are already multiplied by their alpha; defaults to false
+    pub premultiplied_alpha: bool,
+    /// If the drawing buffer keeps its contents after being presented; defaults to false
+    pub preserve_drawing_buffer: bool,
+}
+
+/// The OpenGL API and version to request, see [`GlConfig`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlVersion {
+    /// The newest version of the platform's preferred API
+    Latest,
+    /// A specific version of desktop OpenGL, like `Gl(3, 3)`
+    Gl(u8, u8),
+    /// A specific version of OpenGL ES, like `Gles(3, 0)`
+    Gles(u8, u8),
+}
+
+/// The OpenGL profile to request, see [`GlConfig`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlProfile {
+    /// Only the forward-compatible functions
+    Core,
+    /// Include the deprecated immediate-mode functions
+    Compatibility,
+}
+
+/// The pixel format the platform granted for a window, see [`Window::pixel_format`]
+///
+/// [`Window::pixel_format`]: crate::Window::pixel_format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PixelFormat {
+    /// If the format is hardware-accelerated rather than a software renderer
+    pub hardware_accelerated: bool,
+    /// The number of color bits, not counting alpha
+    pub color_bits: u8,
+    /// The number of alpha bits
+    pub alpha_bits: u8,
+    /// The number of depth buffer bits
+    pub depth_bits: u8,
+    /// The number of stencil buffer bits
+    pub stencil_bits: u8,
+    /// If the framebuffer is double-buffered
+    pub double_buffer: bool,
+    /// How many samples are used for MSAA, if any
+    pub multisampling: Option<u16>,
+    /// If the framebuffer is sRGB-capable
+    pub srgb: bool,
+}
+
+/// The GL context options a window was created with, see [`Window::context_config`]
+///
+/// [`Window::context_config`]: crate::Window::context_config
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContextConfig {
+    /// How many samples are used for MSAA, if any
+    pub multisampling: Option<u16>,
+    /// If vertical sync was requested
+    pub vsync: bool,
+    /// If an sRGB-capable framebuffer was requested
+    pub srgb: bool,
+}
+
+impl ContextConfig {
+    pub(crate) fn requested(settings: &Settings) -> ContextConfig {
+        ContextConfig {
+            multisampling: settings.multisampling,
+            vsync: settings.vsync,
+            srgb: settings.gl.srgb,
+        }
+    }
+
+    /// The configurations to try in order, from the requested one to the safest
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn fallbacks(self) -> Vec<ContextConfig> {
+        let mut configs = vec![self];
+        let steps: [fn(&mut ContextConfig); 3] = [
+            |config| config.multisampling = None,
+            |config| config.vsync = false,
+            |config| config.srgb = false,
+        ];
+        let mut config = self;
+        for step in steps.iter() {
+            step(&mut config);
+            if configs.last() != Some(&config) {
+                configs.push(config);
+            }
+        }
+
+        configs
+    }
+}
+
+/// How often the event loop wakes up the app when there are no new events
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PowerMode {
+    /// Run continuously, delivering a new frame as soon as the last one is processed
+    ///
+    /// This is what most games want, but it keeps a CPU core busy.
+    Poll,
+    /// Only deliver a frame when new events arrive or a timer is due
+    ///
+    /// This suits tools and other mostly-static windows, which only need to redraw on input.
+    /// Gamepads can't wake the event loop, so while one is connected the loop still checks them
+    /// for input every 16ms, delivering a frame only if there was any.
+    Wait,
+    /// Deliver at most this many frames per second, buffering events between frames
+    FrameCap(u32),
+}
+
 /// The options for the cursor icon
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
 pub enum CursorIcon {
+    #[default]
     Default,
     Crosshair,
     Hand,
@@ -87,10 +451,26 @@ pub enum CursorIcon {
     NwseResize,
     ColResize,
     RowResize,
+    /// An image of the app's own, see [`CustomCursor`]
+    Custom(CustomCursor),
 }
 
-impl Default for CursorIcon {
-    fn default() -> CursorIcon {
-        CursorIcon::Default
-    }
+/// How the cursor is held by the window, see [`Window::set_cursor_grab`]
+///
+/// [`Window::set_cursor_grab`]: crate::Window::set_cursor_grab
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CursorGrab {
+    /// The cursor moves freely
+    #[default]
+    None,
+    /// The cursor can't leave the window
+    Confined,
+    /// The cursor is hidden and held in place, for controls like mouselook
+    ///
+    /// Use [`Event::PointerDelta`] to track the mouse's motion, because pointer locations stop
+    /// changing in a meaningful way. On web, this and [`CursorGrab::Confined`] both use the
+    /// pointer lock API.
+    ///
+    /// [`Event::PointerDelta`]: crate::Event::PointerDelta
+    Locked,
 }
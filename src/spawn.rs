@@ -0,0 +1,107 @@
+use futures_executor::{LocalPool, LocalSpawner};
+use futures_util::task::LocalSpawnExt;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+thread_local! {
+    static SPAWNER: RefCell<Option<LocalSpawner>> = const { RefCell::new(None) };
+}
+
+/// Spawn the app onto `pool` and make it the target of [`spawn_local`]
+///
+/// The returned flag is set once the app future completes, regardless of any other tasks.
+pub(crate) fn start_app<T>(pool: &LocalPool, app: T) -> Rc<Cell<bool>>
+where
+    T: 'static + Future<Output = ()>,
+{
+    let finished = Rc::new(Cell::new(false));
+    let flag = finished.clone();
+    let spawner = pool.spawner();
+    spawner
+        .spawn_local(async move {
+            app.await;
+            flag.set(true);
+        })
+        .expect("Failed to start application");
+    SPAWNER.with(|current| current.replace(Some(spawner)));
+
+    finished
+}
+
+/// Stop [`spawn_local`] from targeting the pool of an app that has finished running
+pub(crate) fn finish_app() {
+    SPAWNER.with(|current| current.replace(None));
+}
+
+/// Run a future as a separate task alongside the blinds app
+///
+/// Spawned tasks are polled by the same executor as the app, so they can use the [`Window`] and
+/// timers, and don't need to be `Send`. Use them for work like loading assets or animations
+/// that shouldn't block the main loop.
+///
+/// The returned [`JoinHandle`] resolves to the task's output. Dropping it doesn't cancel the task.
+/// When the app future passed to [`run`] completes, the app exits and any tasks that are still
+/// running are dropped without completing.
+///
+/// # Panics
+///
+/// Panics if called outside of a blinds app.
+///
+/// [`Window`]: crate::Window
+/// [`run`]: crate::run()
+pub fn spawn_local<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: 'static + Future,
+{
+    let state = Rc::new(RefCell::new(JoinState {
+        output: None,
+        waker: None,
+    }));
+    let task_state = state.clone();
+    let task = async move {
+        let output = future.await;
+        let mut state = task_state.borrow_mut();
+        state.output = Some(output);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    };
+    SPAWNER.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .expect("spawn_local must be called from within a blinds app")
+            .spawn_local(task)
+            .expect("Failed to spawn task");
+    });
+
+    JoinHandle { state }
+}
+
+/// A future that resolves to the output of a task started with [`spawn_local`]
+pub struct JoinHandle<T> {
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+struct JoinState<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use futures_util::future::{select, Either};
+use futures_util::pin_mut;
+use futures_util::stream::Stream;
+use instant::Instant;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+thread_local! {
+    static TIMERS: RefCell<Timers> = const { RefCell::new(Timers {
+        entries: BTreeMap::new(),
+        next_id: 0,
+    }) };
+}
+
+/// The pending timers, ordered by deadline and then by registration
+struct Timers {
+    entries: BTreeMap<(Instant, u64), Waker>,
+    next_id: u64,
+}
+
+/// Wake every timer whose deadline has passed, returning if there were any
+pub(crate) fn wake_expired() -> bool {
+    let now = Instant::now();
+    let expired = TIMERS.with(|timers| {
+        let mut timers = timers.borrow_mut();
+        let pending = timers.entries.split_off(&(now, u64::MAX));
+        std::mem::replace(&mut timers.entries, pending)
+    });
+    let any = !expired.is_empty();
+    for waker in expired.into_values() {
+        waker.wake();
+    }
+
+    any
+}
+
+/// The earliest deadline of any pending timer
+pub(crate) fn next_deadline() -> Option<Instant> {
+    TIMERS.with(|timers| {
+        let timers = timers.borrow();
+        timers.entries.keys().next().map(|(deadline, _)| *deadline)
+    })
+}
+
+/// Forget every pending timer, so they can't hold up the next app on this thread
+pub(crate) fn clear() {
+    TIMERS.with(|timers| timers.borrow_mut().entries.clear());
+}
+
+/// A timer's entry in `TIMERS`, which is removed when the timer is dropped
+#[derive(Default)]
+struct Registration {
+    key: Option<(Instant, u64)>,
+}
+
+impl Registration {
+    fn poll(&mut self, deadline: Instant, cx: &mut Context) -> Poll<()> {
+        if Instant::now() >= deadline {
+            self.cancel();
+            return Poll::Ready(());
+        }
+        TIMERS.with(|timers| {
+            let mut timers = timers.borrow_mut();
+            if let Some(key) = self.key {
+                if key.0 == deadline {
+                    // Otherwise it was woken and removed by wake_expired, or cleared
+                    if let Some(waker) = timers.entries.get_mut(&key) {
+                        if !waker.will_wake(cx.waker()) {
+                            *waker = cx.waker().clone();
+                        }
+                        return;
+                    }
+                } else {
+                    timers.entries.remove(&key);
+                }
+            }
+            let key = (deadline, timers.next_id);
+            timers.next_id += 1;
+            timers.entries.insert(key, cx.waker().clone());
+            self.key = Some(key);
+        });
+
+        Poll::Pending
+    }
+
+    fn cancel(&mut self) {
+        if let Some(key) = self.key.take() {
+            // The thread-local is gone if this is dropped while the thread exits
+            let _ = TIMERS.try_with(|timers| timers.borrow_mut().entries.remove(&key));
+        }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Wait until `duration` has passed
+///
+/// Timers are driven by the event loop of [`run`] or [`run_headless`], so they only complete
+/// within a blinds app. While an app is only waiting on timers, the event loop sleeps until the
+/// next deadline instead of spinning.
+///
+/// [`run`]: crate::run()
+/// [`run_headless`]: crate::run_headless()
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: Instant::now() + duration,
+        registration: Registration::default(),
+    }
+}
+
+/// A future that completes at a deadline, created by [`sleep`]
+pub struct Sleep {
+    deadline: Instant,
+    registration: Registration,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let deadline = self.deadline;
+        self.registration.poll(deadline, cx)
+    }
+}
+
+/// Create an [`Interval`] that ticks every `period`, starting one `period` from now
+pub fn interval(period: Duration) -> Interval {
+    Interval {
+        period,
+        next: Instant::now() + period,
+        registration: Registration::default(),
+    }
+}
+
+/// A repeating timer, created by [`interval`]
+///
+/// If a tick is missed because the app was busy, it is skipped rather than delivered late.
+/// `Interval` is also a [`Stream`] that yields once per tick and never ends.
+pub struct Interval {
+    period: Duration,
+    next: Instant,
+    registration: Registration,
+}
+
+impl Interval {
+    /// Wait for the next tick
+    pub async fn tick(&mut self) {
+        futures_util::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    fn poll_tick(&mut self, cx: &mut Context) -> Poll<()> {
+        match self.registration.poll(self.next, cx) {
+            Poll::Ready(()) => {
+                let now = Instant::now();
+                self.next += self.period;
+                if self.next <= now {
+                    self.next = now + self.period;
+                }
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<()>> {
+        self.poll_tick(cx).map(Some)
+    }
+}
+
+/// Run `future`, giving up if it doesn't complete within `duration`
+pub async fn timeout<F: Future>(future: F, duration: Duration) -> Result<F::Output, TimedOut> {
+    pin_mut!(future);
+    match select(future, sleep(duration)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(TimedOut),
+    }
+}
+
+/// The error returned by [`timeout`] when the deadline passes first
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the future did not complete before the deadline")
+    }
+}
+
+impl std::error::Error for TimedOut {}
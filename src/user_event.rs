@@ -0,0 +1,104 @@
+use futures_util::future::poll_fn;
+use futures_util::stream::Stream;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use winit::event_loop::EventLoopProxy;
+
+/// A handle for sending custom events into a blinds app, possibly from other threads
+///
+/// Create one with [`UserEvents::proxy`]. Sending an event wakes the event loop immediately,
+/// even when it is waiting for input because of the [`PowerMode`].
+///
+/// [`PowerMode`]: crate::PowerMode
+pub struct UserEventProxy<U: 'static>(EventLoopProxy<U>);
+
+impl<U: 'static> UserEventProxy<U> {
+    /// Deliver an event to the app's [`UserEvents`]
+    ///
+    /// If the app has already exited, the event is handed back as an error.
+    pub fn send(&self, event: U) -> Result<(), U> {
+        self.0.send_event(event).map_err(|closed| closed.0)
+    }
+}
+
+impl<U: 'static> Clone for UserEventProxy<U> {
+    fn clone(&self) -> Self {
+        UserEventProxy(self.0.clone())
+    }
+}
+
+/// The source of custom events for an app started with [`run_with_user_events`]
+///
+/// Events sent through a [`UserEventProxy`] arrive here, in the order they were sent. This is
+/// separate from the [`EventStream`] so that [`Event`] doesn't need a type parameter; it is also
+/// a [`Stream`] of events that never ends, so the two can be combined with `select`.
+///
+/// [`run_with_user_events`]: crate::run_with_user_events()
+/// [`EventStream`]: crate::EventStream
+/// [`Event`]: crate::Event
+pub struct UserEvents<U: 'static> {
+    buffer: Rc<RefCell<UserBuffer<U>>>,
+    proxy: EventLoopProxy<U>,
+}
+
+impl<U: 'static> UserEvents<U> {
+    pub(crate) fn new(proxy: EventLoopProxy<U>) -> UserEvents<U> {
+        UserEvents {
+            buffer: Rc::new(RefCell::new(UserBuffer {
+                events: VecDeque::new(),
+                waker: None,
+            })),
+            proxy,
+        }
+    }
+
+    pub(crate) fn buffer(&self) -> Rc<RefCell<UserBuffer<U>>> {
+        self.buffer.clone()
+    }
+
+    /// Create a handle that can send events to the app
+    pub fn proxy(&self) -> UserEventProxy<U> {
+        UserEventProxy(self.proxy.clone())
+    }
+
+    /// Wait for the next custom event
+    pub async fn next_user_event(&mut self) -> U {
+        poll_fn(|cx| self.poll_user_event(cx)).await
+    }
+
+    fn poll_user_event(&mut self, cx: &mut Context) -> Poll<U> {
+        let mut buffer = self.buffer.borrow_mut();
+        match buffer.events.pop_front() {
+            Some(event) => Poll::Ready(event),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<U: 'static> Stream for UserEvents<U> {
+    type Item = U;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<U>> {
+        self.poll_user_event(cx).map(Some)
+    }
+}
+
+pub(crate) struct UserBuffer<U> {
+    events: VecDeque<U>,
+    waker: Option<Waker>,
+}
+
+impl<U> UserBuffer<U> {
+    pub fn push(&mut self, event: U) {
+        self.events.push_back(event);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
@@ -1,125 +1,616 @@
-use crate::{CursorIcon, Settings};
+use crate::child::WindowRegistry;
+use crate::event::{Event, FullscreenChangedEvent};
+use crate::geometry::{Geometry, Remembered};
+use crate::headless::HeadlessWindow;
+use crate::monitor::all_monitors;
+use crate::{
+    ContextConfig, CursorGrab, CursorIcon, CustomCursor, Error, FullscreenMode, Icon, Monitor,
+    PixelFormat, PowerMode, Settings, WindowId, WindowPosition,
+};
 #[cfg(not(target_arch = "wasm32"))]
-use glutin::{PossiblyCurrent, WindowedContext};
+use crate::{GlConfig, GlProfile, GlVersion};
+use futures_util::future::poll_fn;
+#[cfg(not(target_arch = "wasm32"))]
+use glutin::{PossiblyCurrent, RawContext, WindowedContext};
 use mint::Vector2;
-use std::sync::Arc;
-use winit::dpi::{LogicalSize, PhysicalSize};
-use winit::event_loop::EventLoop;
+#[cfg(not(target_arch = "wasm32"))]
+use std::cell::Ref;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
+use winit::event_loop::EventLoopWindowTarget;
 use winit::monitor::MonitorHandle;
 use winit::window::{Fullscreen, Window as WinitWindow, WindowBuilder};
 
 /// The Window for your blinds application
-pub struct Window(pub(crate) Arc<WindowContents>);
+///
+/// This is either the main window passed to the app, or a child opened with
+/// [`Window::open_child`]. Dropping every handle to a child window destroys it.
+pub struct Window(pub(crate) Rc<WindowContents>);
 
 pub(crate) struct WindowContents {
+    backend: Backend,
+    id: WindowId,
+    registry: Rc<WindowRegistry>,
+    context_config: ContextConfig,
     #[cfg(target_arch = "wasm32")]
-    window: WinitWindow,
+    webgl_attributes: [(&'static str, bool); 6],
+    power_mode: Cell<PowerMode>,
+    /// The last fullscreen mode reported with an event
+    fullscreen: Cell<FullscreenMode>,
+    /// If the window was last maximized through the settings or `Window::set_maximized`
+    maximized: Cell<bool>,
+    remembered: Option<Remembered>,
+    cursor_icon: RefCell<Option<CursorIcon>>,
+    cursor_grab: Cell<CursorGrab>,
+    focused: Cell<bool>,
+    /// If the window is waiting for its first present to be shown
+    show_on_present: Cell<bool>,
+    aspect_ratio: Cell<Option<f32>>,
+    /// The physical size from the last resize, to tell which side the user is dragging
+    last_size: Cell<PhysicalSize<u32>>,
+    /// The browser's mouse motion since the last frame, because winit doesn't report it on web
+    #[cfg(target_arch = "wasm32")]
+    pointer_motion: Option<PointerMotion>,
+    #[cfg(target_arch = "wasm32")]
+    drop_listener: Option<crate::event::DropListener>,
+    #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+    clipboard: crate::clipboard::Clipboard,
+    #[cfg(all(feature = "clipboard", target_arch = "wasm32"))]
+    paste_listener: Option<crate::clipboard::PasteListener>,
+    exit_on_close: bool,
+    closed: Cell<bool>,
+}
+
+enum Backend {
+    #[cfg(target_arch = "wasm32")]
+    Native(WinitWindow),
     #[cfg(not(target_arch = "wasm32"))]
-    window: WindowedContext<PossiblyCurrent>,
+    Native(NativeWindow),
+    Headless(HeadlessWindow),
+}
+
+/// A desktop window with its GL context split off, so the context can be made current again
+#[cfg(not(target_arch = "wasm32"))]
+struct NativeWindow {
+    // The context has to be dropped before the window it draws to
+    context: RefCell<Option<RawContext<PossiblyCurrent>>>,
+    window: WinitWindow,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NativeWindow {
+    fn new(context: WindowedContext<PossiblyCurrent>) -> NativeWindow {
+        let (context, window) = unsafe { context.split() };
+        NativeWindow {
+            context: RefCell::new(Some(context)),
+            window,
+        }
+    }
+
+    fn context(&self) -> Ref<'_, RawContext<PossiblyCurrent>> {
+        Ref::map(self.context.borrow(), |context| {
+            context
+                .as_ref()
+                .expect("Internal error: GL context missing")
+        })
+    }
+
+    /// The context, after making it current if another window's context is
+    fn current_context(&self) -> Ref<'_, RawContext<PossiblyCurrent>> {
+        if !self.context().is_current() {
+            self.make_current();
+        }
+
+        self.context()
+    }
+
+    fn make_current(&self) {
+        let mut slot = self.context.borrow_mut();
+        let context = slot.take().expect("Internal error: GL context missing");
+        match unsafe { context.make_current() } {
+            Ok(context) => *slot = Some(context),
+            Err((context, err)) => {
+                *slot = Some(context);
+                panic!("Failed to make the GL context current: {}", err);
+            }
+        }
+    }
+}
+
+fn fullscreen_convert(
+    mode: FullscreenMode,
+    mut monitors: impl Iterator<Item = MonitorHandle>,
+    current: Option<MonitorHandle>,
+) -> Option<Fullscreen> {
+    let pick = |index: Option<usize>| match index {
+        Some(index) => monitors.nth(index).or(current),
+        None => current,
+    };
+    match mode {
+        FullscreenMode::Windowed => None,
+        FullscreenMode::Borderless(index) => Some(Fullscreen::Borderless(pick(index))),
+        FullscreenMode::Exclusive {
+            monitor,
+            size,
+            refresh_rate,
+        } => {
+            let monitor = pick(monitor);
+            let video_mode = monitor.as_ref().and_then(|monitor| {
+                monitor
+                    .video_modes()
+                    .filter(|mode| {
+                        mode.size() == PhysicalSize::new(size.x, size.y)
+                            && mode.refresh_rate() == refresh_rate
+                    })
+                    .max_by_key(|mode| mode.bit_depth())
+            });
+            Some(match video_mode {
+                Some(video_mode) => Fullscreen::Exclusive(video_mode),
+                None => Fullscreen::Borderless(monitor),
+            })
+        }
+    }
 }
 
-fn fullscreen_convert(fullscreen: bool, monitor: Option<MonitorHandle>) -> Option<Fullscreen> {
-    if fullscreen {
-        Some(Fullscreen::Borderless(monitor))
-    } else {
-        None
+/// The mode a window is in, with monitors given by their index
+fn fullscreen_mode(window: &WinitWindow) -> FullscreenMode {
+    let index = |monitor: &MonitorHandle| {
+        all_monitors(window.available_monitors(), window.primary_monitor())
+            .iter()
+            .position(|other| other == monitor)
+    };
+    match window.fullscreen() {
+        None => FullscreenMode::Windowed,
+        Some(Fullscreen::Borderless(monitor)) => {
+            FullscreenMode::Borderless(monitor.as_ref().and_then(index))
+        }
+        Some(Fullscreen::Exclusive(video_mode)) => FullscreenMode::Exclusive {
+            monitor: index(&video_mode.monitor()),
+            size: Vector2 {
+                x: video_mode.size().width,
+                y: video_mode.size().height,
+            },
+            refresh_rate: video_mode.refresh_rate(),
+        },
     }
 }
 
+/// The WebGL context attributes to request, as the names the browser expects
 #[cfg(target_arch = "wasm32")]
-fn insert_canvas(window: &WinitWindow, _settings: &Settings) -> web_sys::HtmlCanvasElement {
+fn webgl_attributes(settings: &Settings) -> [(&'static str, bool); 6] {
+    [
+        ("alpha", settings.webgl.alpha || settings.transparent),
+        ("antialias", settings.multisampling.is_some()),
+        ("depth", settings.gl.depth_bits > 0),
+        ("stencil", settings.gl.stencil_bits > 0),
+        ("premultipliedAlpha", settings.webgl.premultiplied_alpha),
+        (
+            "preserveDrawingBuffer",
+            settings.webgl.preserve_drawing_buffer,
+        ),
+    ]
+}
+
+/// Adds up the `movementX` and `movementY` of the canvas's mouse events
+#[cfg(target_arch = "wasm32")]
+struct PointerMotion {
+    delta: Rc<Cell<Vector2<f32>>>,
+    _listener: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::MouseEvent)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PointerMotion {
+    fn track(window: &WinitWindow) -> Result<PointerMotion, Error> {
+        use wasm_bindgen::{closure::Closure, JsCast};
+        use winit::platform::web::WindowExtWebSys;
+        let delta = Rc::new(Cell::new(Vector2 { x: 0.0, y: 0.0 }));
+        let total = delta.clone();
+        let listener = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+            let sum = total.get();
+            total.set(Vector2 {
+                x: sum.x + event.movement_x() as f32,
+                y: sum.y + event.movement_y() as f32,
+            });
+        }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+        window
+            .canvas()
+            .add_event_listener_with_callback("mousemove", listener.as_ref().unchecked_ref())
+            .map_err(|err| js_error("Failed to listen for mouse motion", err))?;
+
+        Ok(PointerMotion {
+            delta,
+            _listener: listener,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn insert_canvas(
+    window: &WinitWindow,
+    settings: &Settings,
+) -> Result<web_sys::HtmlCanvasElement, Error> {
     use winit::platform::web::WindowExtWebSys;
     let canvas = window.canvas();
-    let window = web_sys::window().expect("Failed to obtain window");
-    let document = window.document().expect("Failed to obtain document");
+    let window = web_sys::window().ok_or_else(|| dom_error("Failed to obtain window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| dom_error("Failed to obtain document"))?;
 
     document
         .body()
-        .expect("Document has no body node")
+        .ok_or_else(|| dom_error("Document has no body node"))?
         .append_child(&canvas)
-        .expect("Failed to insert canvas");
+        .map_err(|err| js_error("Failed to insert canvas", err))?;
 
-    canvas.focus().unwrap();
+    canvas
+        .focus()
+        .map_err(|err| js_error("Failed to focus canvas", err))?;
+    if !settings.shown_at_start() {
+        set_canvas_visible(&canvas, false)?;
+    }
 
     #[cfg(feature = "favicon")]
     {
-        if let Some(path) = _settings.icon_path {
-            let head = document.head().expect("Failed to find head node");
-            let element = document
-                .create_element("link")
-                .expect("Failed to create link element");
-            element
-                .set_attribute("rel", "shortcut icon")
-                .expect("Failed to create favicon element");
-            element
-                .set_attribute("type", "image/png")
-                .expect("Failed to create favicon element");
-            element
-                .set_attribute("href", path)
-                .expect("Failed to create favicon element");
-            head.append_child(&element).expect("Failed to add favicon");
+        if let Some(icon) = &settings.icon {
+            set_favicon(&document, icon.links())?;
+        } else if let Some(path) = settings.icon_path {
+            set_favicon(&document, vec![(path.to_owned(), None)])?;
         }
     }
 
+    Ok(canvas)
+}
+
+/// Hide the canvas without taking it out of the page's layout, so its size stays the same
+#[cfg(target_arch = "wasm32")]
+fn set_canvas_visible(canvas: &web_sys::HtmlCanvasElement, visible: bool) -> Result<(), Error> {
+    let visibility = if visible { "visible" } else { "hidden" };
     canvas
+        .style()
+        .set_property("visibility", visibility)
+        .map_err(|err| js_error("Failed to set the canvas visibility", err))
+}
+
+/// Replace the page's favicons with links to the given `href`s, with optional `sizes`
+#[cfg(all(feature = "favicon", target_arch = "wasm32"))]
+fn set_favicon(
+    document: &web_sys::Document,
+    links: Vec<(String, Option<String>)>,
+) -> Result<(), Error> {
+    while let Ok(Some(old)) = document.query_selector("link[rel~='icon']") {
+        old.remove();
+    }
+    let head = document
+        .head()
+        .ok_or_else(|| dom_error("Failed to find head node"))?;
+    for (href, sizes) in links {
+        let element = document
+            .create_element("link")
+            .map_err(|err| js_error("Failed to create link element", err))?;
+        element
+            .set_attribute("rel", "shortcut icon")
+            .and_then(|_| element.set_attribute("type", "image/png"))
+            .and_then(|_| element.set_attribute("href", &href))
+            .and_then(|_| match &sizes {
+                Some(sizes) => element.set_attribute("sizes", sizes),
+                None => Ok(()),
+            })
+            .map_err(|err| js_error("Failed to create favicon element", err))?;
+        head.append_child(&element)
+            .map_err(|err| js_error("Failed to add favicon", err))?;
+    }
+
+    Ok(())
 }
 
-fn settings_to_wb(el: &EventLoop<()>, settings: &Settings) -> WindowBuilder {
-    #[cfg(feature = "image")]
-    let icon = settings.icon_path.map(|path| {
-        let img = image::open(path).expect("Failed to load image");
-        let rgba = img.to_rgba8();
-        let (width, height) = rgba.dimensions();
-        let buffer = rgba.into_raw();
-
-        winit::window::Icon::from_rgba(buffer, width, height).expect("Bad image data")
-    });
-    #[cfg(not(feature = "image"))]
+#[cfg(target_arch = "wasm32")]
+fn dom_error(msg: &str) -> Error {
+    Error::Dom(msg.to_owned())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn js_error(msg: &str, err: wasm_bindgen::JsValue) -> Error {
+    Error::Dom(format!("{}: {:?}", msg, err))
+}
+
+fn settings_to_wb<T>(
+    el: &EventLoopWindowTarget<T>,
+    settings: &Settings,
+) -> Result<WindowBuilder, Error> {
+    // On web, the icon is used as the favicon instead
+    #[cfg(not(target_arch = "wasm32"))]
+    let icon = match &settings.icon {
+        Some(icon) => icon.to_winit(),
+        #[cfg(feature = "image")]
+        None => match settings.icon_path {
+            Some(path) => Icon::from_path(path)?.to_winit(),
+            None => None,
+        },
+        #[cfg(not(feature = "image"))]
+        None => None,
+    };
+    #[cfg(target_arch = "wasm32")]
     let icon = None;
 
     let scale = el.primary_monitor().map_or(1.0, |m| m.scale_factor());
 
-    WindowBuilder::new()
+    let mut wb = WindowBuilder::new()
         .with_inner_size(PhysicalSize {
             width: settings.size.x as f64 * scale,
             height: settings.size.y as f64 * scale,
         })
         .with_resizable(settings.resizable)
+        .with_decorations(settings.decorations)
+        .with_always_on_top(settings.always_on_top)
+        .with_maximized(settings.maximized)
+        .with_visible(settings.shown_at_start())
+        .with_transparent(settings.transparent)
         .with_fullscreen(fullscreen_convert(
             settings.fullscreen,
+            all_monitors(el.available_monitors(), el.primary_monitor()).into_iter(),
             el.primary_monitor(),
         ))
-        .with_title(settings.title)
-        .with_window_icon(icon)
+        .with_title(&settings.title)
+        .with_window_icon(icon);
+    if let Some(size) = settings.min_size {
+        wb = wb.with_min_inner_size(to_logical_size(size));
+    }
+    if let Some(size) = settings.max_size {
+        wb = wb.with_max_inner_size(to_logical_size(size));
+    }
+
+    Ok(wb)
+}
+
+fn to_logical_size(size: Vector2<f32>) -> LogicalSize<f64> {
+    LogicalSize {
+        width: size.x as f64,
+        height: size.y as f64,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn build_context<T>(
+    el: &EventLoopWindowTarget<T>,
+    wb: WindowBuilder,
+    gl: &GlConfig,
+    config: ContextConfig,
+    shared: Option<&glutin::Context<PossiblyCurrent>>,
+) -> Result<WindowedContext<PossiblyCurrent>, Error> {
+    let version = match gl.version {
+        GlVersion::Latest => glutin::GlRequest::Latest,
+        GlVersion::Gl(major, minor) => {
+            glutin::GlRequest::Specific(glutin::Api::OpenGl, (major, minor))
+        }
+        GlVersion::Gles(major, minor) => {
+            glutin::GlRequest::Specific(glutin::Api::OpenGlEs, (major, minor))
+        }
+    };
+    let mut cb = glutin::ContextBuilder::new()
+        .with_gl(version)
+        .with_gl_debug_flag(gl.debug)
+        .with_depth_buffer(gl.depth_bits)
+        .with_stencil_buffer(gl.stencil_bits)
+        .with_pixel_format(24, gl.alpha_bits)
+        .with_vsync(config.vsync)
+        .with_srgb(config.srgb);
+    if let Some(profile) = gl.profile {
+        cb = cb.with_gl_profile(match profile {
+            GlProfile::Core => glutin::GlProfile::Core,
+            GlProfile::Compatibility => glutin::GlProfile::Compatibility,
+        });
+    }
+    if let Some(msaa) = config.multisampling {
+        cb = cb.with_multisampling(msaa);
+    }
+    match shared {
+        Some(shared) => {
+            let window = cb.with_shared_lists(shared).build_windowed(wb, el)?;
+            // Leave the current context alone, the app switches with Window::make_current
+            Ok(unsafe { window.treat_as_current() })
+        }
+        None => {
+            let window = cb.build_windowed(wb, el)?;
+            unsafe { window.make_current() }
+                .map_err(|(_, err)| Error::ContextCreation(Box::new(err)))
+        }
+    }
 }
 
 impl WindowContents {
-    pub(crate) fn new(el: &EventLoop<()>, settings: Settings) -> WindowContents {
-        let wb = settings_to_wb(el, &settings);
+    pub(crate) fn new<T>(
+        el: &EventLoopWindowTarget<T>,
+        settings: Settings,
+    ) -> Result<WindowContents, Error> {
+        let registry = Rc::new(WindowRegistry::new());
+        WindowContents::build(el, settings, WindowId::MAIN, registry, None)
+    }
+
+    /// Open the child windows the app asked for, returning if there were any
+    pub(crate) fn open_children<T>(&self, el: &EventLoopWindowTarget<T>) -> bool {
+        let requests = self.registry.take_requests();
+        for request in requests.iter() {
+            let mut request = request.borrow_mut();
+            let settings = request.take_settings();
+            let id = self.registry.next_id();
+            let result = WindowContents::build(el, settings, id, self.registry.clone(), Some(self));
+            request.fulfill(result.map(|child| {
+                let child = Rc::new(child);
+                self.registry.add(&child);
+                Window(child)
+            }));
+        }
+
+        !requests.is_empty()
+    }
+
+    fn build<T>(
+        el: &EventLoopWindowTarget<T>,
+        mut settings: Settings,
+        id: WindowId,
+        registry: Rc<WindowRegistry>,
+        main: Option<&WindowContents>,
+    ) -> Result<WindowContents, Error> {
+        let remembered = Remembered::restore(el, &mut settings);
+        let wb = settings_to_wb(el, &settings)?;
+        let requested = ContextConfig::requested(&settings);
+        #[cfg(target_arch = "wasm32")]
+        let pointer_motion;
         #[cfg(target_arch = "wasm32")]
-        let window = {
-            let window = wb.build(el).expect("Failed to create window");
-            insert_canvas(&window, &settings);
-            WindowContents { window }
+        let drop_listener;
+        #[cfg(all(feature = "clipboard", target_arch = "wasm32"))]
+        let paste_listener;
+        #[cfg(target_arch = "wasm32")]
+        let (backend, config) = {
+            // WebGL contexts can't be shared between canvases
+            let _ = main;
+            let window = wb
+                .build(el)
+                .map_err(|err| Error::WindowCreation(Box::new(err)))?;
+            insert_canvas(&window, &settings)?;
+            pointer_motion = Some(PointerMotion::track(&window)?);
+            drop_listener = {
+                use winit::platform::web::WindowExtWebSys;
+                Some(
+                    crate::event::DropListener::listen(&window.canvas())
+                        .map_err(|err| js_error("Failed to listen for dropped files", err))?,
+                )
+            };
+            #[cfg(feature = "clipboard")]
+            {
+                use winit::platform::web::WindowExtWebSys;
+                paste_listener = Some(
+                    crate::clipboard::PasteListener::listen(&window.canvas())
+                        .map_err(|err| js_error("Failed to listen for pastes", err))?,
+                );
+            }
+            (Backend::Native(window), requested)
         };
         #[cfg(not(target_arch = "wasm32"))]
-        let window = {
-            let mut cb = glutin::ContextBuilder::new().with_vsync(settings.vsync);
-            if let Some(msaa) = settings.multisampling {
-                cb = cb.with_multisampling(msaa);
+        let (backend, config) = {
+            let shared = main.and_then(|main| match &main.backend {
+                Backend::Native(native) => Some(native.context()),
+                Backend::Headless(_) => None,
+            });
+            let shared = shared.as_deref().map(|context| &**context);
+            let mut gl = settings.gl;
+            if settings.transparent {
+                gl.alpha_bits = gl.alpha_bits.max(8);
             }
-            let window = cb.build_windowed(wb, el).expect("Failed to create window");
-            let window = unsafe { window.make_current().expect("Failed to acquire GL context") };
-            WindowContents { window }
+            let configs = if settings.context_fallback {
+                requested.fallbacks()
+            } else {
+                vec![requested]
+            };
+            let mut result = Err(Error::ContextCreation("No configurations to try".into()));
+            for config in configs {
+                result = build_context(el, wb.clone(), &gl, config, shared)
+                    .map(|window| (window, config));
+                if result.is_ok() {
+                    break;
+                }
+            }
+            let (window, config) = result?;
+            (Backend::Native(NativeWindow::new(window)), config)
         };
-        window.set_cursor_icon(settings.cursor_icon);
-        window.set_title(settings.title);
+        let mut window = WindowContents::with_backend(backend, &settings, id, registry);
+        window.context_config = config;
+        #[cfg(target_arch = "wasm32")]
+        {
+            window.pointer_motion = pointer_motion;
+            window.drop_listener = drop_listener;
+            #[cfg(feature = "clipboard")]
+            {
+                window.paste_listener = paste_listener;
+            }
+        }
+        // The requested mode may have been resolved to a specific monitor, or not be possible
+        window.fullscreen.set(fullscreen_mode(window.window()));
+        window.last_size.set(window.window().inner_size());
+        window.set_cursor_icon(settings.cursor_icon.clone());
+        window.set_title(&settings.title);
+        window.place(settings.position);
+        window.remembered = remembered;
+        window.update_geometry();
 
-        window
+        Ok(window)
+    }
+
+    pub(crate) fn headless(settings: &Settings) -> WindowContents {
+        let registry = Rc::new(WindowRegistry::new());
+        WindowContents::headless_with(settings, WindowId::MAIN, registry)
+    }
+
+    fn headless_with(
+        settings: &Settings,
+        id: WindowId,
+        registry: Rc<WindowRegistry>,
+    ) -> WindowContents {
+        // There's no GL context to fall back from, so report the requested one
+        let backend = Backend::Headless(HeadlessWindow::new(settings));
+        WindowContents::with_backend(backend, settings, id, registry)
+    }
+
+    fn with_backend(
+        backend: Backend,
+        settings: &Settings,
+        id: WindowId,
+        registry: Rc<WindowRegistry>,
+    ) -> WindowContents {
+        WindowContents {
+            backend,
+            id,
+            registry,
+            context_config: ContextConfig::requested(settings),
+            #[cfg(target_arch = "wasm32")]
+            webgl_attributes: webgl_attributes(settings),
+            power_mode: Cell::new(settings.power_mode),
+            fullscreen: Cell::new(settings.fullscreen),
+            maximized: Cell::new(settings.maximized),
+            remembered: None,
+            cursor_icon: RefCell::new(settings.cursor_icon.clone()),
+            cursor_grab: Cell::new(CursorGrab::None),
+            focused: Cell::new(false),
+            show_on_present: Cell::new(settings.visible && settings.show_after_present),
+            aspect_ratio: Cell::new(settings.aspect_ratio),
+            last_size: Cell::new(PhysicalSize::new(0, 0)),
+            #[cfg(target_arch = "wasm32")]
+            pointer_motion: None,
+            #[cfg(target_arch = "wasm32")]
+            drop_listener: None,
+            #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+            clipboard: crate::clipboard::Clipboard::new(),
+            #[cfg(all(feature = "clipboard", target_arch = "wasm32"))]
+            paste_listener: None,
+            exit_on_close: settings.exit_on_close,
+            closed: Cell::new(false),
+        }
     }
 
     fn set_cursor_icon(&self, icon: Option<CursorIcon>) {
-        match icon {
+        if let Backend::Headless(headless) = &self.backend {
+            return headless.set_cursor_icon(icon);
+        }
+
+        match &icon {
+            // A locked cursor stays hidden until it's released
+            Some(_) if self.cursor_grab.get() == CursorGrab::Locked => {
+                self.window().set_cursor_visible(false);
+            }
+            #[cfg(target_arch = "wasm32")]
+            Some(CursorIcon::Custom(cursor)) => {
+                use winit::platform::web::WindowExtWebSys;
+                self.window().set_cursor_visible(true);
+                if let Some(css) = cursor.css() {
+                    let _ = self.window().canvas().style().set_property("cursor", &css);
+                }
+            }
+            // Desktop platforms can't show custom cursors, so the app draws its software_cursor
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(CursorIcon::Custom(_)) => {
+                self.window().set_cursor_visible(false);
+            }
             Some(icon) => {
                 self.window().set_cursor_visible(true);
                 self.window().set_cursor_icon(icon_to_winit(icon));
@@ -128,9 +619,111 @@ impl WindowContents {
                 self.window().set_cursor_visible(false);
             }
         }
+        self.cursor_icon.replace(icon);
+    }
+
+    fn software_cursor(&self) -> Option<CustomCursor> {
+        if cfg!(target_arch = "wasm32") || self.cursor_grab.get() == CursorGrab::Locked {
+            return None;
+        }
+        match &*self.cursor_icon.borrow() {
+            Some(CursorIcon::Custom(cursor)) => Some(cursor.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_cursor_grab(&self, grab: CursorGrab) -> bool {
+        if let Backend::Headless(headless) = &self.backend {
+            headless.set_cursor_grab(grab);
+            self.cursor_grab.set(grab);
+            return true;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let grabbed = self
+            .window()
+            .set_cursor_grab(grab != CursorGrab::None)
+            .is_ok();
+        #[cfg(target_arch = "wasm32")]
+        let grabbed = {
+            use winit::platform::web::WindowExtWebSys;
+            match grab {
+                CursorGrab::None => web_sys::window()
+                    .and_then(|window| window.document())
+                    .map(|document| document.exit_pointer_lock())
+                    .is_some(),
+                CursorGrab::Confined | CursorGrab::Locked => {
+                    self.window().canvas().request_pointer_lock();
+                    true
+                }
+            }
+        };
+        if grabbed {
+            self.cursor_grab.set(grab);
+            self.set_cursor_icon(self.cursor_icon.borrow().clone());
+        }
+
+        grabbed
+    }
+
+    /// Keep a locked cursor in the middle of the window, so it never hits an edge
+    ///
+    /// Browsers do this themselves with the pointer lock API.
+    pub(crate) fn hold_cursor(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.cursor_grab.get() == CursorGrab::Locked && self.focused.get() {
+            if let Backend::Native(native) = &self.backend {
+                let size = native.window.inner_size();
+                let center = PhysicalPosition::new(size.width / 2, size.height / 2);
+                // Some platforms, like Wayland, can't move the cursor
+                let _ = native.window.set_cursor_position(center);
+            }
+        }
+    }
+
+    pub(crate) fn set_focused(&self, focused: bool) {
+        self.focused.set(focused);
+    }
+
+    pub(crate) fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    /// Move the window according to `Settings::position`, now that its size is known
+    fn place(&self, position: WindowPosition) {
+        let window = self.window();
+        let position = match position {
+            WindowPosition::Auto => return,
+            WindowPosition::At(position) => position,
+            WindowPosition::Centered(index) => {
+                let monitor = match index {
+                    Some(index) => {
+                        all_monitors(window.available_monitors(), window.primary_monitor())
+                            .into_iter()
+                            .nth(index)
+                    }
+                    None => window.primary_monitor(),
+                };
+                let monitor = match monitor {
+                    Some(monitor) => monitor,
+                    None => return,
+                };
+                let (origin, area, size) =
+                    (monitor.position(), monitor.size(), window.outer_size());
+                Vector2 {
+                    x: origin.x + (area.width as i32 - size.width as i32) / 2,
+                    y: origin.y + (area.height as i32 - size.height as i32) / 2,
+                }
+            }
+        };
+        window.set_outer_position(PhysicalPosition::new(position.x, position.y));
     }
 
     fn set_title(&self, title: &str) {
+        if let Backend::Headless(headless) = &self.backend {
+            return headless.set_title(title);
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         self.window().set_title(title);
 
@@ -142,21 +735,204 @@ impl WindowContents {
             .set_title(title);
     }
 
+    fn set_visible(&self, visible: bool) {
+        self.show_on_present.set(false);
+        if let Backend::Headless(headless) = &self.backend {
+            return headless.set_visible(visible);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.window().set_visible(visible);
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::WindowExtWebSys;
+            let _ = set_canvas_visible(&self.window().canvas(), visible);
+        }
+    }
+
+    fn set_icon(&self, icon: Option<Icon>) {
+        if let Backend::Headless(headless) = &self.backend {
+            return headless.set_icon(icon);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.window()
+            .set_window_icon(icon.as_ref().and_then(Icon::to_winit));
+
+        // Failing to update the favicon isn't worth interrupting the app for
+        #[cfg(all(feature = "favicon", target_arch = "wasm32"))]
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            let links = icon.as_ref().map(Icon::links).unwrap_or_default();
+            let _ = set_favicon(&document, links);
+        }
+        #[cfg(all(not(feature = "favicon"), target_arch = "wasm32"))]
+        let _ = icon;
+    }
+
+    /// Correct the size of a window with a locked aspect ratio after it's resized
+    pub(crate) fn keep_aspect_ratio(&self, size: PhysicalSize<u32>) {
+        let previous = self.last_size.replace(size);
+        let ratio = match self.aspect_ratio.get() {
+            Some(ratio) if ratio > 0.0 => ratio,
+            _ => return,
+        };
+        // The page decides the canvas size on web, a fullscreen window fills the screen, and a
+        // maximized one would be pulled out of being maximized
+        if cfg!(target_arch = "wasm32")
+            || size.width == 0
+            || size.height == 0
+            || self.fullscreen().is_fullscreen()
+            || self.maximized.get()
+        {
+            return;
+        }
+        let change = |now: u32, before: u32| (now as i64 - before as i64).abs();
+        let corrected =
+            if change(size.width, previous.width) >= change(size.height, previous.height) {
+                PhysicalSize::new(size.width, (size.width as f32 / ratio).round() as u32)
+            } else {
+                PhysicalSize::new((size.height as f32 * ratio).round() as u32, size.height)
+            };
+        // Leave room for rounding, so the window doesn't keep resizing by a pixel
+        if change(corrected.width, size.width) > 1 || change(corrected.height, size.height) > 1 {
+            self.window().set_inner_size(corrected);
+        }
+    }
+
     pub(crate) fn resize(&self, _size: PhysicalSize<u32>) {
         #[cfg(not(target_arch = "wasm32"))]
-        self.window.resize(_size);
+        if let Backend::Native(native) = &self.backend {
+            native.context().resize(_size);
+        }
     }
 
     pub(crate) fn scale(&self) -> f32 {
-        self.window().scale_factor() as f32
+        match &self.backend {
+            Backend::Native(_) => self.window().scale_factor() as f32,
+            Backend::Headless(headless) => headless.scale_factor(),
+        }
+    }
+
+    pub(crate) fn power_mode(&self) -> PowerMode {
+        self.power_mode.get()
+    }
+
+    fn fullscreen(&self) -> FullscreenMode {
+        match &self.backend {
+            Backend::Native(_) => fullscreen_mode(self.window()),
+            Backend::Headless(headless) => headless.fullscreen(),
+        }
+    }
+
+    /// The events that blinds detects by checking the window, rather than getting from winit
+    pub(crate) fn poll_events(&self) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mode = self.fullscreen();
+        if mode != self.fullscreen.replace(mode) {
+            events.push(Event::FullscreenChanged(FullscreenChangedEvent { mode }));
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(listener) = &self.drop_listener {
+            events.extend(listener.take());
+        }
+        #[cfg(all(feature = "clipboard", target_arch = "wasm32"))]
+        if let Some(listener) = &self.paste_listener {
+            events.extend(listener.take().into_iter().map(Event::Paste));
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(motion) = &self.pointer_motion {
+            let delta = motion.delta.replace(Vector2 { x: 0.0, y: 0.0 });
+            if self.focused.get() && (delta.x != 0.0 || delta.y != 0.0) {
+                events.push(Event::PointerDelta(crate::event::PointerDeltaEvent {
+                    delta,
+                }));
+            }
+        }
+
+        events
     }
 
+    pub(crate) fn id(&self) -> WindowId {
+        self.id
+    }
+
+    pub(crate) fn registry(&self) -> &WindowRegistry {
+        &self.registry
+    }
+
+    /// If this is the native window that winit knows by `id`
+    pub(crate) fn is_native(&self, id: winit::window::WindowId) -> bool {
+        match &self.backend {
+            Backend::Native(_) => self.window().id() == id,
+            Backend::Headless(_) => false,
+        }
+    }
+
+    /// Note that the user asked to close the window, closing it if the settings allow
+    pub(crate) fn close_requested(&self) {
+        if self.exit_on_close {
+            self.close();
+        }
+    }
+
+    /// Keep track of the window's geometry, if it should be remembered
+    pub(crate) fn update_geometry(&self) {
+        if let Some(remembered) = &self.remembered {
+            let window = self.window();
+            // Maximizing does nothing on web, so the canvas's size is always its windowed size
+            let maximized = self.maximized.get() && cfg!(not(target_arch = "wasm32"));
+            remembered.update(Geometry::capture(
+                window,
+                fullscreen_mode(window),
+                maximized,
+            ));
+        }
+    }
+
+    /// Write the window's geometry, if it should be remembered
+    pub(crate) fn save_geometry(&self) {
+        if let Some(remembered) = &self.remembered {
+            remembered.save();
+        }
+    }
+
+    fn close(&self) {
+        if !self.closed.get() {
+            self.update_geometry();
+            self.save_geometry();
+        }
+        self.closed.set(true);
+        // The main window closes along with the app, but children have to disappear on their own
+        if self.id != WindowId::MAIN {
+            if let Backend::Native(_) = &self.backend {
+                self.window().set_visible(false);
+            }
+        }
+    }
+
+    /// If the window was closed; for the main window, the app exits at the end of the frame
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.get()
+    }
+
+    pub(crate) fn headless_window(&self) -> Option<&HeadlessWindow> {
+        match &self.backend {
+            Backend::Native(_) => None,
+            Backend::Headless(headless) => Some(headless),
+        }
+    }
+
+    /// The native window; only call this after handling the headless case
     #[inline]
     fn window(&self) -> &WinitWindow {
-        #[cfg(target_arch = "wasm32")]
-        return &self.window;
-        #[cfg(not(target_arch = "wasm32"))]
-        return self.window.window();
+        match &self.backend {
+            #[cfg(target_arch = "wasm32")]
+            Backend::Native(window) => window,
+            #[cfg(not(target_arch = "wasm32"))]
+            Backend::Native(native) => &native.window,
+            Backend::Headless(_) => unreachable!("Headless windows have no native window"),
+        }
     }
 }
 
@@ -166,6 +942,32 @@ impl Window {
         self.0.set_cursor_icon(icon);
     }
 
+    /// The [`CustomCursor`] the app should draw itself, because the platform can't show it
+    ///
+    /// This is the cursor set with [`CursorIcon::Custom`] on desktop, unless the cursor is
+    /// locked. Draw it each frame at the pointer location, offset by its hotspot. On web, and
+    /// in headless mode, it's always `None`.
+    pub fn software_cursor(&self) -> Option<CustomCursor> {
+        if self.0.headless_window().is_some() {
+            return None;
+        }
+        self.0.software_cursor()
+    }
+
+    /// Confine or lock the cursor to the window, or release it
+    ///
+    /// Returns false if the platform doesn't support grabbing the cursor. On web, the browser
+    /// only grants a lock in response to a user interaction like a click, and the user can
+    /// release it at any time with escape.
+    pub fn set_cursor_grab(&self, grab: CursorGrab) -> bool {
+        self.0.set_cursor_grab(grab)
+    }
+
+    /// How the cursor is currently held by the window
+    pub fn cursor_grab(&self) -> CursorGrab {
+        self.0.cursor_grab.get()
+    }
+
     /// Get the size of the window in logical units
     ///
     /// On a high-dpi display, this doesn't correspond to physical pixels and must be multiplied by
@@ -173,6 +975,9 @@ impl Window {
     ///
     /// [`scale`]: Window::scale_factor
     pub fn size(&self) -> Vector2<f32> {
+        if let Some(headless) = self.0.headless_window() {
+            return headless.size();
+        }
         let size = self.0.window().inner_size();
         let size: LogicalSize<f64> = size.to_logical(self.0.window().scale_factor());
         Vector2 {
@@ -192,14 +997,171 @@ impl Window {
 
     /// Set the size of the inside of the window in logical units
     pub fn set_size(&self, size: Vector2<f32>) {
+        if let Some(headless) = self.0.headless_window() {
+            return headless.set_size(size);
+        }
         let scale = self.0.window().scale_factor();
-        self.0.window().set_inner_size(
-            LogicalSize {
-                width: size.x as f64,
-                height: size.y as f64,
-            }
-            .to_physical::<f64>(scale),
-        );
+        self.0
+            .window()
+            .set_inner_size(to_logical_size(size).to_physical::<f64>(scale));
+    }
+
+    /// Set the smallest logical size the user can resize the window to, or remove the limit (None)
+    ///
+    /// Does nothing on web
+    pub fn set_min_size(&self, size: Option<Vector2<f32>>) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0
+            .window()
+            .set_min_inner_size(size.map(to_logical_size));
+    }
+
+    /// Set the largest logical size the user can resize the window to, or remove the limit (None)
+    ///
+    /// Does nothing on web
+    pub fn set_max_size(&self, size: Option<Vector2<f32>>) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0
+            .window()
+            .set_max_inner_size(size.map(to_logical_size));
+    }
+
+    /// Lock the ratio of the window's width to its height, or unlock it (None)
+    ///
+    /// The window is resized to match straight away, keeping its width. See
+    /// [`Settings::aspect_ratio`] for the caveats.
+    ///
+    /// [`Settings::aspect_ratio`]: crate::Settings::aspect_ratio
+    pub fn set_aspect_ratio(&self, ratio: Option<f32>) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0.aspect_ratio.set(ratio);
+        let size = self.0.window().inner_size();
+        self.0.last_size.set(size);
+        self.0.keep_aspect_ratio(size);
+    }
+
+    /// The position of the window's top-left corner on the desktop, in physical pixels
+    ///
+    /// Physical pixels are used so the position can be compared with [`Monitor::position`]. On
+    /// web this is the canvas's position in the page. Returns None if the platform doesn't
+    /// allow reading it, like Wayland.
+    pub fn position(&self) -> Option<Vector2<i32>> {
+        if let Some(headless) = self.0.headless_window() {
+            return Some(headless.position());
+        }
+        let position = self.0.window().outer_position().ok()?;
+        Some(Vector2 {
+            x: position.x,
+            y: position.y,
+        })
+    }
+
+    /// Move the window's top-left corner to a position in physical pixels
+    ///
+    /// This un-maximizes the window. Does nothing on platforms that don't allow it, like Wayland.
+    pub fn set_position(&self, position: Vector2<i32>) {
+        if let Some(headless) = self.0.headless_window() {
+            return headless.set_position(position);
+        }
+        self.0
+            .window()
+            .set_outer_position(PhysicalPosition::new(position.x, position.y));
+    }
+
+    /// Show the window, if it was hidden
+    pub fn show(&self) {
+        self.0.set_visible(true);
+    }
+
+    /// Hide the window, without closing it
+    ///
+    /// On web the canvas is hidden but keeps its place in the page.
+    pub fn hide(&self) {
+        self.0.set_visible(false);
+    }
+
+    /// Minimize the window, or restore it from being minimized
+    ///
+    /// Does nothing on web
+    pub fn set_minimized(&self, minimized: bool) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0.window().set_minimized(minimized);
+    }
+
+    /// Maximize the window, or restore it from being maximized
+    ///
+    /// Does nothing on web
+    pub fn set_maximized(&self, maximized: bool) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0.maximized.set(maximized);
+        self.0.window().set_maximized(maximized);
+    }
+
+    /// Show or hide the window's title bar and borders
+    ///
+    /// Does nothing on web
+    pub fn set_decorations(&self, decorations: bool) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0.window().set_decorations(decorations);
+    }
+
+    /// Keep the window above other windows, or let them cover it again
+    ///
+    /// Does nothing on web
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        if self.0.headless_window().is_some() {
+            return;
+        }
+        self.0.window().set_always_on_top(always_on_top);
+    }
+
+    /// Read the text on the system clipboard
+    ///
+    /// Returns None if the clipboard doesn't hold text or can't be reached. On web this uses the
+    /// async Clipboard API, so the browser may ask the user for permission first; the
+    /// [`Event::Paste`] that a paste delivers doesn't need any.
+    ///
+    /// [`Event::Paste`]: crate::Event::Paste
+    #[cfg(feature = "clipboard")]
+    pub async fn clipboard_text(&self) -> Option<String> {
+        if let Some(headless) = self.0.headless_window() {
+            return headless.clipboard_text();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.0.clipboard.read(self.0.window());
+
+        #[cfg(target_arch = "wasm32")]
+        return crate::clipboard::read().await;
+    }
+
+    /// Put some text on the system clipboard, returning false if it couldn't be reached
+    ///
+    /// On web, browsers only allow this in response to a user interaction, like a key press.
+    #[cfg(feature = "clipboard")]
+    pub async fn set_clipboard_text(&self, text: &str) -> bool {
+        if let Some(headless) = self.0.headless_window() {
+            headless.set_clipboard_text(text);
+            return true;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.0.clipboard.write(self.0.window(), text);
+
+        #[cfg(target_arch = "wasm32")]
+        return crate::clipboard::write(text).await;
     }
 
     /// Set the title of the window or browser tab
@@ -207,60 +1169,261 @@ impl Window {
         self.0.set_title(title);
     }
 
-    /// Set if the window should be fullscreen or not
+    /// Set the icon of the window, or remove it (None)
+    ///
+    /// On web this replaces the page's favicons, if the `favicon` feature is enabled.
+    pub fn set_icon(&self, icon: Option<Icon>) {
+        self.0.set_icon(icon);
+    }
+
+    /// Set if the window should be fullscreen or not, and how
+    ///
+    /// Passing a bool picks between borderless fullscreen on the current monitor and windowed.
+    /// On desktop, the change happens instantly. On web, it will become fullscreen after the next
+    /// user interaction, due to browser API restrictions. Either way, an
+    /// [`Event::FullscreenChanged`] reports the mode the window ends up in.
     ///
-    /// On desktop, it will instantly become fullscreen (borderless windowed on Windows and Linux,
-    /// and fullscreen on macOS). On web, it will become fullscreen after the next user
-    /// interaction, due to browser API restrictions.
-    pub fn set_fullscreen(&self, fullscreen: bool) {
-        self.0.window().set_fullscreen(fullscreen_convert(
-            fullscreen,
-            self.0.window().current_monitor(),
+    /// [`Event::FullscreenChanged`]: crate::Event::FullscreenChanged
+    pub fn set_fullscreen(&self, mode: impl Into<FullscreenMode>) {
+        let mode = mode.into();
+        if let Some(headless) = self.0.headless_window() {
+            return headless.set_fullscreen(mode);
+        }
+        let window = self.0.window();
+        window.set_fullscreen(fullscreen_convert(
+            mode,
+            all_monitors(window.available_monitors(), window.primary_monitor()).into_iter(),
+            window.current_monitor(),
         ));
     }
 
+    /// The fullscreen mode the window is currently in
+    ///
+    /// Monitors are always given by index. Headless windows report the last mode they were given.
+    pub fn fullscreen(&self) -> FullscreenMode {
+        self.0.fullscreen()
+    }
+
+    /// The monitor the window is mostly on, if it can be determined
+    ///
+    /// Returns None when running headless.
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        if self.0.headless_window().is_some() {
+            return None;
+        }
+        self.0.window().current_monitor().map(Monitor)
+    }
+
+    /// The monitor the operating system considers primary, if there is one
+    ///
+    /// Returns None when running headless.
+    pub fn primary_monitor(&self) -> Option<Monitor> {
+        if self.0.headless_window().is_some() {
+            return None;
+        }
+        self.0.window().primary_monitor().map(Monitor)
+    }
+
+    /// All of the monitors connected to the computer
+    ///
+    /// On web this is the single placeholder monitor. Returns an empty list when running headless.
+    pub fn monitors(&self) -> Vec<Monitor> {
+        if self.0.headless_window().is_some() {
+            return Vec::new();
+        }
+        let window = self.0.window();
+        all_monitors(window.available_monitors(), window.primary_monitor())
+            .into_iter()
+            .map(Monitor)
+            .collect()
+    }
+
+    /// The GL context options the window was actually created with
+    ///
+    /// This differs from the [`Settings`] if [`Settings::context_fallback`] had to degrade them.
+    pub fn context_config(&self) -> ContextConfig {
+        self.0.context_config
+    }
+
+    /// The pixel format the platform granted for the window's framebuffer
+    ///
+    /// This can have more bits or samples than [`Settings::gl`] asked for. Returns None on web and
+    /// in headless apps, where the format isn't known.
+    pub fn pixel_format(&self) -> Option<PixelFormat> {
+        match &self.0.backend {
+            #[cfg(not(target_arch = "wasm32"))]
+            Backend::Native(native) => {
+                let format = native.context().get_pixel_format();
+                Some(PixelFormat {
+                    hardware_accelerated: format.hardware_accelerated,
+                    color_bits: format.color_bits,
+                    alpha_bits: format.alpha_bits,
+                    depth_bits: format.depth_bits,
+                    stencil_bits: format.stencil_bits,
+                    double_buffer: format.double_buffer,
+                    multisampling: format.multisampling,
+                    srgb: format.srgb,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Close the window and exit the app at the end of the current frame
+    ///
+    /// Tasks that are still running, including the app itself, are dropped.
+    ///
+    /// Closing a child window doesn't exit the app: the child is hidden immediately and stops
+    /// receiving events. It is destroyed once every handle to it has been dropped.
+    pub fn close(&self) {
+        self.0.close();
+    }
+
+    /// Identifies this window in the events from [`EventStream::next_tagged_event`]
+    ///
+    /// [`EventStream::next_tagged_event`]: crate::EventStream::next_tagged_event
+    pub fn id(&self) -> WindowId {
+        self.0.id
+    }
+
+    /// Open another window on the same event loop, like a detachable palette
+    ///
+    /// The child is created at the end of the current frame. Its events arrive in the same
+    /// [`EventStream`] as the main window's, tagged with its [`id`]. The user closing a child
+    /// window never exits the app; [`Settings::exit_on_close`] decides whether it closes or only
+    /// delivers [`Event::CloseRequested`].
+    ///
+    /// On desktop, the child's GL context shares textures, buffers and shaders with the main
+    /// window's, but it isn't made current: call [`make_current`] before drawing to it. On web,
+    /// the child is another canvas with its own WebGL context.
+    ///
+    /// [`EventStream`]: crate::EventStream
+    /// [`id`]: Window::id
+    /// [`Event::CloseRequested`]: crate::Event::CloseRequested
+    /// [`make_current`]: Window::make_current
+    pub async fn open_child(&self, settings: Settings) -> Result<Window, Error> {
+        if self.0.headless_window().is_some() {
+            let id = self.0.registry.next_id();
+            let child = WindowContents::headless_with(&settings, id, self.0.registry.clone());
+            let child = Rc::new(child);
+            self.0.registry.add(&child);
+            return Ok(Window(child));
+        }
+        let request = self.0.registry.request(settings);
+        poll_fn(|cx| request.borrow_mut().poll(cx)).await
+    }
+
+    /// Make this window's GL context the target of OpenGL calls
+    ///
+    /// Only needed when there are child windows; the main window's context is current until
+    /// another one is made current. [`present`] and [`get_proc_address`] make their window's
+    /// context current themselves. Does nothing on web and when running headless.
+    ///
+    /// [`present`]: Window::present
+    /// [`get_proc_address`]: Window::get_proc_address
+    pub fn make_current(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Backend::Native(native) = &self.0.backend {
+            native.make_current();
+        }
+    }
+
+    /// Change how often the event loop runs when nothing is happening
+    ///
+    /// This takes effect at the end of the current frame. See [`PowerMode`] for the options.
+    pub fn set_power_mode(&self, mode: PowerMode) {
+        self.0.power_mode.set(mode);
+    }
+
+    /// The current [`PowerMode`] of the event loop
+    pub fn power_mode(&self) -> PowerMode {
+        self.0.power_mode()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     /// Return the address of a given OpenGL function
+    ///
+    /// This makes the window's GL context current, if it isn't already. When running headless
+    /// there is no GL context, so this always returns null.
     pub fn get_proc_address(&self, func: &str) -> *const core::ffi::c_void {
-        self.0.window.get_proc_address(func)
+        match &self.0.backend {
+            Backend::Native(native) => native.current_context().get_proc_address(func),
+            Backend::Headless(_) => std::ptr::null(),
+        }
     }
 
     #[cfg(target_arch = "wasm32")]
     /// Create a WebGL context from the backing canvas
+    ///
+    /// The context is created with the attributes from the [`Settings`].
+    ///
+    /// Panics when running headless, because there is no canvas.
     pub fn webgl_context(&self) -> web_sys::WebGlRenderingContext {
+        use wasm_bindgen::JsCast;
+        self.canvas_context("webgl")
+            .expect("Failed to acquire a WebGL rendering context")
+            .dyn_into::<web_sys::WebGlRenderingContext>()
+            .expect("WebGL context of unexpected type")
+    }
+
+    /// Get a WebGL2 context for the canvas, if the browser supports WebGL2
+    ///
+    /// The context is created with the attributes from the [`Settings`]. A canvas only has one
+    /// context, so if this returns None, [`Window::webgl_context`] can be used as a fallback.
+    ///
+    /// Panics when running headless, because there is no canvas.
+    #[cfg(target_arch = "wasm32")]
+    pub fn webgl2_context(&self) -> Option<web_sys::WebGl2RenderingContext> {
+        use wasm_bindgen::JsCast;
+        self.canvas_context("webgl2").map(|context| {
+            context
+                .dyn_into::<web_sys::WebGl2RenderingContext>()
+                .expect("WebGL2 context of unexpected type")
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn canvas_context(&self, kind: &str) -> Option<js_sys::Object> {
         use js_sys::{Map, Object};
-        use wasm_bindgen::{JsCast, JsValue};
+        use wasm_bindgen::JsValue;
         use winit::platform::web::WindowExtWebSys;
         let map = Map::new();
-        map.set(&JsValue::from_str("premultipliedAlpha"), &JsValue::FALSE);
-        map.set(&JsValue::from_str("alpha"), &JsValue::FALSE);
+        for (name, value) in self.0.webgl_attributes.iter() {
+            map.set(&JsValue::from_str(name), &JsValue::from_bool(*value));
+        }
         let props = Object::from_entries(&map).expect("Failed to create object");
 
         self.0
-            .window
+            .window()
             .canvas()
-            .get_context_with_context_options("webgl", &props)
-            .expect("Failed to acquire a WebGL rendering context")
-            .expect("Failed to acquire a WebGL rendering context")
-            .dyn_into::<web_sys::WebGlRenderingContext>()
-            .expect("WebGL context of unexpected type")
+            .get_context_with_context_options(kind, &props)
+            .expect("Failed to acquire a rendering context")
     }
 
     /// Draw the OpenGL frame to the screen
     ///
     /// If vsync is enabled, this will block until the frame is completed on desktop. On web, there
     /// is no way to control vsync, or to manually control presentation, so this function is a
-    /// no-op.
+    /// no-op. It is also a no-op when running headless.
+    ///
+    /// On desktop this makes the window's GL context current, if it isn't already, so that its own
+    /// buffers are swapped. The first call shows a window created with
+    /// [`Settings::show_after_present`], on every platform.
     pub fn present(&self) {
         #[cfg(not(target_arch = "wasm32"))]
-        self.0
-            .window
-            .swap_buffers()
-            .expect("Failed to swap buffers")
+        if let Backend::Native(native) = &self.0.backend {
+            native
+                .current_context()
+                .swap_buffers()
+                .expect("Failed to swap buffers")
+        }
+        if self.0.show_on_present.get() {
+            self.0.set_visible(true);
+        }
     }
 }
 
-fn icon_to_winit(cursor: CursorIcon) -> winit::window::CursorIcon {
+fn icon_to_winit(cursor: &CursorIcon) -> winit::window::CursorIcon {
     use CursorIcon::*;
     match cursor {
         Default => winit::window::CursorIcon::Default,
@@ -298,5 +1461,6 @@ fn icon_to_winit(cursor: CursorIcon) -> winit::window::CursorIcon {
         NwseResize => winit::window::CursorIcon::NwseResize,
         ColResize => winit::window::CursorIcon::ColResize,
         RowResize => winit::window::CursorIcon::RowResize,
+        Custom(_) => unreachable!("Custom cursors have no system equivalent"),
     }
 }
@@ -0,0 +1,44 @@
+use blinds::{run_headless, Event, EventStream, HeadlessInput, Key, Settings, Window};
+use mint::Vector2;
+
+#[test]
+fn events_arrive_in_their_frames() {
+    let mut input = HeadlessInput::new();
+    input.resize(Vector2 { x: 800.0, y: 600.0 }).end_frame();
+    input.character('h').character('i').end_frame();
+    input.idle_frames(2);
+    input.key(Key::Return, true).end_frame();
+
+    let window = run_headless(Settings::default(), input, log_frames);
+    assert_eq!(window.title(), "[resized] [hi] [] [] [enter]");
+    assert_eq!(window.size(), Vector2 { x: 800.0, y: 600.0 });
+}
+
+#[test]
+fn closing_stops_the_frames() {
+    let mut input = HeadlessInput::new();
+    input.character('a').end_frame();
+    input.close_requested().end_frame();
+    input.character('b').end_frame();
+
+    let window = run_headless(Settings::default(), input, log_frames);
+    assert_eq!(window.title(), "[a] []");
+}
+
+/// Keep a log of each frame's events in the title
+async fn log_frames(window: Window, mut events: EventStream) {
+    let mut frames = Vec::new();
+    let mut frame = String::new();
+    loop {
+        match events.next_event().await {
+            Some(Event::Resized(_)) => frame += "resized",
+            Some(Event::ReceivedCharacter(ev)) => frame.push(ev.character()),
+            Some(Event::KeyboardInput(ev)) if ev.key() == Key::Return => frame += "enter",
+            Some(_) => (),
+            None => {
+                frames.push(format!("[{}]", std::mem::take(&mut frame)));
+                window.set_title(&frames.join(" "));
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use blinds::{run_headless, EventStream, HeadlessInput, Key, Settings, Window};
+use futures_util::StreamExt;
+
+fn input() -> HeadlessInput {
+    let mut input = HeadlessInput::new();
+    input.character('a').character('b').end_frame();
+    input.end_frame();
+    input.key(Key::A, true).close_requested().end_frame();
+    input.character('c').end_frame();
+
+    input
+}
+
+#[test]
+fn frames_end_with_the_window() {
+    let window = run_headless(Settings::default(), input(), count_events);
+    assert_eq!(window.title(), "2 0 2");
+}
+
+#[cfg(feature = "event-cache")]
+#[test]
+fn cached_frames_end_with_the_window() {
+    use blinds::CachedEventStream;
+
+    let window = run_headless(Settings::default(), input(), |window, events| async move {
+        let mut events = CachedEventStream::new(events);
+        let mut counts = Vec::new();
+        while let Some(frame) = events.next().await {
+            counts.push(frame.events().len().to_string());
+            if events.cache().key(Key::A) {
+                counts.push("A".to_owned());
+            }
+            window.set_title(&counts.join(" "));
+        }
+    });
+    assert_eq!(window.title(), "2 0 2 A");
+}
+
+/// Write the number of events in each frame to the title
+async fn count_events(window: Window, mut events: EventStream) {
+    let mut counts = Vec::new();
+    while let Some(frame) = events.next().await {
+        counts.push(frame.events().len().to_string());
+        window.set_title(&counts.join(" "));
+    }
+}
@@ -0,0 +1,32 @@
+use blinds::{run_headless, timeout, EventStream, HeadlessInput, Settings, Window};
+use std::time::{Duration, Instant};
+
+#[test]
+fn dropped_timers_do_not_outlive_the_app() {
+    let mut input = HeadlessInput::new();
+    for _ in 0..10 {
+        input.character('a').end_frame();
+    }
+    let window = run_headless(Settings::default(), input, |window, events| {
+        frame_timeouts(window, events)
+    });
+    assert_eq!(window.title(), "aaaaaaaaaa");
+
+    // Nothing is left for this app to wait on, so it shouldn't block on the timeouts above
+    let start = Instant::now();
+    let mut input = HeadlessInput::new();
+    input.idle_frames(3);
+    run_headless(Settings::default(), input, |_, _| std::future::pending());
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+async fn frame_timeouts(window: Window, mut events: EventStream) {
+    let mut title = String::new();
+    // A fresh timeout for every event, each dropped before it expires
+    while let Ok(event) = timeout(events.next_event(), Duration::from_secs(3)).await {
+        if let Some(blinds::Event::ReceivedCharacter(ev)) = event {
+            title.push(ev.character());
+            window.set_title(&title);
+        }
+    }
+}